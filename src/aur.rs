@@ -5,3 +5,6 @@
 //! Work with the Arch User Repository.
 
 pub mod rpc;
+
+#[cfg(test)]
+pub(crate) mod mock;
@@ -0,0 +1,182 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A minimal local HTTP server to test AUR RPC requests without the AUR.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use super::rpc::AurPackage;
+
+/// The AUR package `1password`, from the fixture of an info response.
+///
+/// Override fields of the package to test checks on AUR packages.
+pub(crate) fn package_1password() -> AurPackage {
+    let info: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/info-1password.json")).unwrap();
+    serde_json::from_value(info["results"][0].clone()).unwrap()
+}
+
+/// A raw HTTP response with the given `status` line and JSON `body`.
+pub(crate) fn json_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// Keep the connection of a raw HTTP `response` open.
+pub(crate) fn keep_alive(response: String) -> String {
+    response.replace("Connection: close", "Connection: keep-alive")
+}
+
+/// Serve HTTP requests on a local port until the test ends.
+///
+/// Call `respond` with the target of every request, and send back the raw HTTP response it
+/// returns, which may be a string or raw bytes.  Return the base URL of the RPC interface on the
+/// server, and the targets of all requests served so far.
+pub(crate) async fn serve<F, R>(respond: F) -> (reqwest::Url, Arc<Mutex<Vec<String>>>)
+where
+    F: FnMut(&str) -> R + Send + 'static,
+    R: Into<Vec<u8>>,
+{
+    let (url, requests, _) = serve_connections(respond).await;
+    (url, requests)
+}
+
+/// Like [`serve`], but also count the connections accepted so far.
+///
+/// Serve connections one after another, and keep a connection open for further requests until
+/// the client closes it, or a response has `Connection: close`; see [`keep_alive`].
+pub(crate) async fn serve_connections<F, R>(
+    mut respond: F,
+) -> (reqwest::Url, Arc<Mutex<Vec<String>>>, Arc<AtomicUsize>)
+where
+    F: FnMut(&str) -> R + Send + 'static,
+    R: Into<Vec<u8>>,
+{
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let served = requests.clone();
+    let (url, _, connections) = serve_heads(move |head| {
+        let target = head.split(' ').nth(1).unwrap().to_string();
+        let response = respond(&target);
+        served.lock().unwrap().push(target);
+        response
+    })
+    .await;
+    (url, requests, connections)
+}
+
+/// Like [`serve_connections`], but with the whole head of every request.
+///
+/// Call `respond` with the request line and the headers of every request, and return these
+/// request heads instead of just the targets, e.g. to check the HTTP version of requests.
+pub(crate) async fn serve_heads<F, R>(
+    respond: F,
+) -> (reqwest::Url, Arc<Mutex<Vec<String>>>, Arc<AtomicUsize>)
+where
+    F: FnMut(&str) -> R + Send + 'static,
+    R: Into<Vec<u8>>,
+{
+    serve_heads_after(Duration::ZERO, respond).await
+}
+
+/// Like [`serve_heads`], but wait for `delay` before sending every response.
+pub(crate) async fn serve_delayed<F, R>(
+    delay: Duration,
+    respond: F,
+) -> (reqwest::Url, Arc<Mutex<Vec<String>>>)
+where
+    F: FnMut(&str) -> R + Send + 'static,
+    R: Into<Vec<u8>>,
+{
+    let (url, heads, _) = serve_heads_after(delay, respond).await;
+    (url, heads)
+}
+
+/// Serve requests like [`serve_heads`], and send every response after `delay`.
+async fn serve_heads_after<F, R>(
+    delay: Duration,
+    mut respond: F,
+) -> (reqwest::Url, Arc<Mutex<Vec<String>>>, Arc<AtomicUsize>)
+where
+    F: FnMut(&str) -> R + Send + 'static,
+    R: Into<Vec<u8>>,
+{
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/rpc/", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let connections = Arc::new(AtomicUsize::new(0));
+    let served = requests.clone();
+    let accepted = connections.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            accepted.fetch_add(1, Ordering::SeqCst);
+            'connection: loop {
+                let mut request = Vec::new();
+                while !request.ends_with(b"\r\n\r\n") {
+                    let mut buffer = [0; 1024];
+                    let n = stream.read(&mut buffer).await.unwrap_or(0);
+                    if n == 0 {
+                        break 'connection;
+                    }
+                    request.extend_from_slice(&buffer[..n]);
+                }
+                let head = String::from_utf8(request).unwrap();
+                let response = respond(&head).into();
+                served.lock().unwrap().push(head);
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                stream.write_all(&response).await.unwrap();
+                let close = b"Connection: close";
+                if response.windows(close.len()).any(|window| window == close) {
+                    break;
+                }
+            }
+        }
+    });
+    (url.parse().unwrap(), requests, connections)
+}
+
+/// A local port which never completes any new connection.
+///
+/// Listen without ever accepting connections, and fill up the accept backlog, so that connecting
+/// to [`Unresponsive::url`] hangs until the client gives up.  Keep this value alive for as long
+/// as the port should stay unresponsive.
+pub(crate) struct Unresponsive {
+    /// The base URL of the RPC interface on the unresponsive port.
+    pub(crate) url: reqwest::Url,
+    _listener: tokio::net::TcpListener,
+    _connections: Vec<tokio::net::TcpStream>,
+}
+
+/// Create an [`Unresponsive`] port.
+pub(crate) async fn unresponsive() -> Unresponsive {
+    let socket = tokio::net::TcpSocket::new_v4().unwrap();
+    socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    let listener = socket.listen(0).unwrap();
+    let addr = listener.local_addr().unwrap();
+    // Connect until a connection no longer completes, because the backlog is full
+    let mut connections = Vec::new();
+    while let Ok(connection) = tokio::time::timeout(
+        Duration::from_millis(100),
+        tokio::net::TcpStream::connect(addr),
+    )
+    .await
+    {
+        connections.push(connection.unwrap());
+    }
+    Unresponsive {
+        url: format!("http://{}/rpc/", addr).parse().unwrap(),
+        _listener: listener,
+        _connections: connections,
+    }
+}
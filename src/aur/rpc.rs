@@ -4,24 +4,85 @@
 
 //! The RPC interface of the Arch User Repository.
 
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use thiserror::Error;
 use tracing::{event, instrument, Level};
 
 /// The user agent to use for RPC requests to the AUR.
 static USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
-/// The letsencrypt root certificate.
+/// The letsencrypt root certificates, ISRG Root X1 and ISRG Root X2.
 ///
-/// The AUR RPC endpoint uses letsencrypt certificates, so this is the only relevant root
-/// certificate we require for AUR RPC requests.
+/// The AUR RPC endpoint uses letsencrypt certificates, so these are the only relevant root
+/// certificates we require for AUR RPC requests.  We include both letsencrypt roots, so that
+/// we continue to work if letsencrypt changes the chain of the AUR certificate to the other root.
 ///
-/// Embedding this certificate makes aur-trust self-contained and independent from system trust
+/// Embedding these certificates makes aur-trust self-contained and independent from system trust
 /// stores, and increases security because we avoid all other shady CAs that might be installed
 /// somewhere.
-static LETSENCRYPT_ROOT: &[u8] = include_bytes!("isrgrootx1.der");
+static LETSENCRYPT_ROOTS: [&[u8]; 2] = [
+    include_bytes!("isrgrootx1.der"),
+    include_bytes!("isrgrootx2.der"),
+];
+
+/// Load the embedded letsencrypt root certificates.
+fn letsencrypt_roots() -> Result<Vec<reqwest::tls::Certificate>> {
+    LETSENCRYPT_ROOTS
+        .iter()
+        .map(|der| reqwest::tls::Certificate::from_der(der).map_err(AurRpcError::Certificate))
+        .collect()
+}
+
+/// The URL of the AUR, for git repositories of packages.
+static AUR_URL: &str = "https://aur.archlinux.org/";
+
+/// The URL of the AUR RPC interface.
+static DEFAULT_BASE_URL: &str = "https://aur.archlinux.org/rpc/";
+
+/// The version of the AUR RPC interface we use.
+static RPC_VERSION: &str = "5";
+
+/// The default timeout for AUR RPC requests.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The default number of packages to request information for at once.
+const DEFAULT_BATCH_SIZE: usize = 200;
 
-fn letsencrypt_root() -> reqwest::tls::Certificate {
-    reqwest::tls::Certificate::from_der(LETSENCRYPT_ROOT).unwrap()
+/// The default number of retries for failed AUR RPC requests.
+const DEFAULT_RETRIES: u32 = 3;
+
+/// The base delay before retrying a failed request.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+
+/// The default minimum TLS version for AUR RPC requests.
+const DEFAULT_MIN_TLS_VERSION: reqwest::tls::Version = reqwest::tls::Version::TLS_1_3;
+
+/// A client builder with our user agent and pinned TLS configuration.
+///
+/// If `system_roots` is true, trust the root certificates of the system instead of pinning the
+/// letsencrypt roots.
+fn pinned_client_builder(system_roots: bool) -> Result<reqwest::ClientBuilder> {
+    let builder = reqwest::ClientBuilder::new()
+        .user_agent(USER_AGENT)
+        .referer(false)
+        .use_rustls_tls()
+        .tls_built_in_root_certs(system_roots);
+    if system_roots {
+        Ok(builder)
+    } else {
+        // Only use letsencrypt root certificates, because that's what AUR uses
+        Ok(letsencrypt_roots()?
+            .into_iter()
+            .fold(builder, |builder, root| builder.add_root_certificate(root)))
+    }
 }
 
 /// Information about an AUR package.
@@ -30,11 +91,57 @@ fn letsencrypt_root() -> reqwest::tls::Certificate {
 pub struct AurPackage {
     /// The package name
     pub name: String,
+    /// The name of the package base of this package.
+    ///
+    /// Split packages share a package base, which is the git repository of the package.
+    pub package_base: String,
+    /// The numeric ID of the package base of this package.
+    ///
+    /// Note that the AUR RPC interface cannot look up packages by their package base ID; use
+    /// [`AurRpcClient::info`] with package names instead.
+    #[serde(rename = "PackageBaseID")]
+    pub package_base_id: u64,
+    /// When the package was flagged out of date, as unix timestamp, if it's out of date.
+    pub out_of_date: Option<i64>,
+    /// The number of votes for this package.
+    pub num_votes: u32,
+    /// The popularity of this package.
+    pub popularity: f64,
+    /// When the package was first submitted to the AUR, as unix timestamp.
+    pub first_submitted: i64,
+    /// When the package was last modified, as unix timestamp.
+    pub last_modified: i64,
     /// The main maintainer of the package.
-    pub maintainer: String,
+    ///
+    /// `None` if the package is orphaned.
+    pub maintainer: Option<String>,
     /// All registered co-maintainers of the package.
     #[serde(default)]
     pub co_maintainers: Vec<String>,
+    /// Packages this package depends on at runtime.
+    #[serde(default)]
+    pub depends: Vec<String>,
+    /// Packages required to build this package.
+    #[serde(default)]
+    pub make_depends: Vec<String>,
+    /// Packages required to run the tests of this package.
+    #[serde(default)]
+    pub check_depends: Vec<String>,
+    /// Packages which this package provides.
+    #[serde(default)]
+    pub provides: Vec<String>,
+    /// Keywords of this package.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+impl AurPackage {
+    /// The URL of the git repository of the package base of this package.
+    pub fn git_url(&self) -> reqwest::Url {
+        reqwest::Url::parse(AUR_URL)
+            .and_then(|url| url.join(&format!("{}.git", self.package_base)))
+            .expect("Git URL should be valid")
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -48,89 +155,959 @@ struct AurInfo {
 /// A request to the AUR RPC interface failed.
 #[derive(Error, Debug)]
 pub enum AurRpcError {
-    /// Reqwest returned an error.
-    #[error("reqwest failed")]
-    ReqwestError(#[from] reqwest::Error),
+    /// Reqwest returned an error, e.g. because it failed to connect to the AUR.
+    #[error("AUR request failed")]
+    ReqwestError(#[source] reqwest::Error),
+    /// An embedded root certificate was invalid.
+    #[error("invalid embedded root certificate")]
+    Certificate(#[source] reqwest::Error),
+    /// The AUR responded with an HTTP error status.
+    #[error("AUR responded with HTTP status {status}")]
+    Http {
+        /// The HTTP status of the response.
+        status: reqwest::StatusCode,
+        /// The underlying error.
+        #[source]
+        source: reqwest::Error,
+    },
+    /// The response of the AUR was not valid JSON, or did not have the expected structure.
+    #[error("failed to decode AUR response")]
+    Decode(#[source] reqwest::Error),
+    /// The request timed out.
+    #[error("request timed out")]
+    Timeout(#[source] reqwest::Error),
+    /// The AUR rejected the request because of rate limiting.
+    #[error("rate limited, retry after {retry_after:?}")]
+    RateLimited {
+        /// How long to wait before retrying, according to the AUR.
+        ///
+        /// Zero if the AUR did not say how long to wait.
+        retry_after: Duration,
+    },
+    /// The AUR returned more than one result for a query about a single package.
+    #[error("expected at most one result for package {package}, got {count}")]
+    UnexpectedResult {
+        /// The package we requested information about.
+        package: String,
+        /// The number of results returned by the AUR.
+        count: usize,
+    },
+}
+
+impl From<reqwest::Error> for AurRpcError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            AurRpcError::Timeout(error)
+        } else if let Some(status) = error.status() {
+            AurRpcError::Http {
+                status,
+                source: error,
+            }
+        } else if error.is_decode() {
+            AurRpcError::Decode(error)
+        } else {
+            AurRpcError::ReqwestError(error)
+        }
+    }
 }
 
 /// The result of AUR RPC requests.
 pub type Result<T> = std::result::Result<T, AurRpcError>;
 
+/// The field to search packages by.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SearchBy {
+    /// Search by package name.
+    Name,
+    /// Search by package name and description.
+    NameDesc,
+    /// Search by the maintainer of packages.
+    Maintainer,
+    /// Search for packages which depend on a package.
+    Depends,
+    /// Search for packages which make-depend on a package.
+    MakeDepends,
+    /// Search for packages which optionally depend on a package.
+    OptDepends,
+    /// Search for packages which check-depend on a package.
+    CheckDepends,
+}
+
+impl SearchBy {
+    /// The value of the `by` parameter for this field.
+    fn as_str(self) -> &'static str {
+        match self {
+            SearchBy::Name => "name",
+            SearchBy::NameDesc => "name-desc",
+            SearchBy::Maintainer => "maintainer",
+            SearchBy::Depends => "depends",
+            SearchBy::MakeDepends => "makedepends",
+            SearchBy::OptDepends => "optdepends",
+            SearchBy::CheckDepends => "checkdepends",
+        }
+    }
+}
+
+/// The HTTP version to use for AUR RPC requests.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum HttpVersion {
+    /// Negotiate the HTTP version with ALPN during the TLS handshake.
+    #[default]
+    Auto,
+    /// Only use HTTP/1.
+    Http1,
+    /// Use HTTP/2 with prior knowledge, i.e. without negotiating the version first.
+    ///
+    /// This saves a little latency but fails if anything between us and the AUR, e.g. a proxy,
+    /// does not support HTTP/2.
+    Http2,
+}
+
 /// A client for the AUR RPC interface.
 #[derive(Debug, Clone)]
 pub struct AurRpcClient {
     client: reqwest::Client,
+    base_url: reqwest::Url,
+    batch_size: usize,
+    retries: u32,
+    cache: Option<Arc<InfoCache>>,
+}
+
+/// A cache for information about packages, by package name.
+#[derive(Debug)]
+struct InfoCache {
+    /// How long to keep cached packages.
+    ttl: Duration,
+    /// Cached packages, with the time they were cached.
+    packages: Mutex<HashMap<String, (Instant, AurPackage)>>,
+}
+
+impl InfoCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            packages: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the cached information about `package`, unless it has expired.
+    fn get(&self, package: &str) -> Option<AurPackage> {
+        let packages = self.packages.lock().unwrap();
+        packages
+            .get(package)
+            .filter(|(cached_at, _)| cached_at.elapsed() < self.ttl)
+            .map(|(_, package)| package.clone())
+    }
+
+    /// Cache the given `package`.
+    fn insert(&self, package: &AurPackage) {
+        let mut packages = self.packages.lock().unwrap();
+        packages.insert(package.name.clone(), (Instant::now(), package.clone()));
+    }
 }
 
 impl AurRpcClient {
     /// Create a new default AUR client.
     ///
     /// This client uses a user agent which identifies aur-trust and its version number, and a
-    /// custom TLS configuration which uses only the letsencrypt root certificate required to make
-    /// secure AUR RPC connections.
+    /// custom TLS configuration which uses only the letsencrypt root certificates required to
+    /// make secure AUR RPC connections.
+    ///
+    /// Requests time out after 30 seconds.
     pub fn new() -> Result<Self> {
-        reqwest::ClientBuilder::new()
-            .user_agent(USER_AGENT)
-            .referer(false)
-            .use_rustls_tls()
-            // Only use letsencrypt root certificate, because that's what AUR uses
-            .tls_built_in_root_certs(false)
-            .add_root_certificate(letsencrypt_root())
-            .min_tls_version(reqwest::tls::Version::TLS_1_3)
-            .build()
-            .map(Self::from_client)
-            .map_err(From::from)
+        Self::builder().build()
+    }
+
+    /// Create a builder to configure a new AUR client.
+    ///
+    /// See [`AurRpcClient::new`] for the default configuration.
+    pub fn builder() -> AurRpcClientBuilder {
+        AurRpcClientBuilder::new()
+    }
+
+    /// Create a new AUR client which uses the given HTTP `version`.
+    ///
+    /// See [`AurRpcClient::new`] for the remaining configuration.
+    pub fn with_http_version(version: HttpVersion) -> Result<Self> {
+        Self::builder().http_version(version).build()
+    }
+
+    /// Create a new AUR client which resolves `host` to `addr` instead of using DNS.
+    ///
+    /// See [`AurRpcClientBuilder::resolve`] and [`AurRpcClient::new`] for the remaining
+    /// configuration.
+    pub fn with_resolve(host: &str, addr: SocketAddr) -> Result<Self> {
+        Self::builder().resolve(host, addr).build()
+    }
+
+    /// Create a new AUR client whose requests time out after `timeout`.
+    ///
+    /// See [`AurRpcClientBuilder::timeout`] and [`AurRpcClient::new`] for the remaining
+    /// configuration.
+    pub fn with_timeout(timeout: Duration) -> Result<Self> {
+        Self::builder().timeout(timeout).build()
+    }
+
+    /// Create a new AUR client which asks for compressed responses if `compression` is true.
+    ///
+    /// See [`AurRpcClientBuilder::compression`] and [`AurRpcClient::new`] for the remaining
+    /// configuration.
+    pub fn with_compression(compression: bool) -> Result<Self> {
+        Self::builder().compression(compression).build()
+    }
+
+    /// Create a new AUR client which gives up connecting to the AUR after `timeout`.
+    ///
+    /// See [`AurRpcClientBuilder::connect_timeout`] and [`AurRpcClient::new`] for the remaining
+    /// configuration.
+    pub fn with_connect_timeout(timeout: Duration) -> Result<Self> {
+        Self::builder().connect_timeout(timeout).build()
+    }
+
+    /// Create a new AUR client which sends requests to `base_url` instead of the AUR.
+    ///
+    /// See [`AurRpcClientBuilder::base_url`] and [`AurRpcClient::new`] for the remaining
+    /// configuration.
+    pub fn with_base_url(base_url: reqwest::Url) -> Result<Self> {
+        Self::builder().base_url(base_url).build()
     }
 
     /// Create an AUR RPC client around the given [`reqwest::Client`].
     pub fn from_client(client: reqwest::Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            base_url: with_rpc_version(
+                reqwest::Url::parse(DEFAULT_BASE_URL)
+                    .expect("Base URL should definitely be valid!"),
+            ),
+            batch_size: DEFAULT_BATCH_SIZE,
+            retries: DEFAULT_RETRIES,
+            cache: None,
+        }
     }
 
     /// The AUR RPC base URL, for version 5.
     fn base_url(&self) -> reqwest::Url {
-        // TODO: Find a way to make this static?
-        reqwest::Url::parse("https://aur.archlinux.org/rpc/?v=5")
-            .expect("Base URL should definitely be valid!")
+        self.base_url.clone()
     }
 
     /// Get information about the given `packages`.
+    ///
+    /// Split `packages` into batches according to [`AurRpcClientBuilder::batch_size`], and request
+    /// information about every batch separately.
+    ///
+    /// If the client has a [cache](AurRpcClientBuilder::cache), return cached packages first, and only
+    /// request information about the remaining packages.
+    ///
+    /// If `packages` is empty, return no packages without making any request.
     #[instrument(skip_all)]
     pub async fn info<I, S>(&self, packages: I) -> Result<Vec<AurPackage>>
     where
         I: IntoIterator<Item = S>,
         S: AsRef<str>,
     {
-        let mut url = self.base_url();
-        url.query_pairs_mut().append_pair("type", "info");
-        for package in packages {
-            url.query_pairs_mut().append_pair("arg[]", package.as_ref());
+        self.info_with_count(packages)
+            .await
+            .map(|(_, results)| results)
+    }
+
+    /// Get information about the given `packages`, along with the result count of the AUR.
+    ///
+    /// Like [`AurRpcClient::info`], but also return the sum of the result counts the AUR reported
+    /// for all batches, plus the number of cached packages.  The count should equal the number of
+    /// returned packages; if it does not, the AUR sent an inconsistent response, e.g. with
+    /// missing or duplicate packages.
+    #[instrument(skip_all)]
+    pub async fn info_with_count<I, S>(&self, packages: I) -> Result<(usize, Vec<AurPackage>)>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let packages = packages.into_iter().collect::<Vec<_>>();
+        let mut cached = Vec::new();
+        let mut missing = Vec::with_capacity(packages.len());
+        for package in &packages {
+            match self.cache.as_ref().and_then(|c| c.get(package.as_ref())) {
+                Some(info) => {
+                    event!(Level::DEBUG, "Cache hit for package {}", package.as_ref());
+                    cached.push(info);
+                }
+                None => missing.push(package.as_ref()),
+            }
         }
-        event!(Level::DEBUG, "GET {}", &url);
-        let info: AurInfo = self
-            .client
-            .get(url)
-            .send()
+        let mut resultcount = 0;
+        let mut results = Vec::with_capacity(missing.len());
+        for batch in missing.chunks(self.batch_size) {
+            let mut url = self.base_url();
+            url.query_pairs_mut().append_pair("type", "info");
+            for package in batch {
+                url.query_pairs_mut().append_pair("arg[]", package);
+            }
+            let info: AurInfo = self.get_json(url).await?;
+            resultcount += info.resultcount;
+            results.extend(info.results);
+        }
+        warn_inconsistent_resultcount(resultcount, &results);
+        if let Some(cache) = &self.cache {
+            for package in &results {
+                cache.insert(package);
+            }
+        }
+        let count = cached.len() + resultcount;
+        cached.extend(results);
+        Ok((count, cached))
+    }
+
+    /// Get information about a single `package`.
+    ///
+    /// Return `None` if the package does not exist in the AUR, and fail with
+    /// [`AurRpcError::UnexpectedResult`] if the AUR returns more than one package.
+    pub async fn info_one(&self, package: &str) -> Result<Option<AurPackage>> {
+        single_result(package, self.info(&[package]).await?)
+    }
+
+    /// Search for packages whose field `by` matches `term`.
+    #[instrument(skip(self))]
+    pub async fn search(&self, by: SearchBy, term: &str) -> Result<Vec<AurPackage>> {
+        let mut url = self.base_url();
+        url.query_pairs_mut()
+            .append_pair("type", "search")
+            .append_pair("by", by.as_str())
+            .append_pair("arg", term);
+        let info: AurInfo = self.get_json(url).await?;
+        warn_inconsistent_resultcount(info.resultcount, &info.results);
+        Ok(info.results)
+    }
+
+    /// Get information about all packages maintained by `maintainer`.
+    ///
+    /// Search packages by maintainer, and then get full information about all packages found,
+    /// because search results lack some information, e.g. co-maintainers and dependencies.
+    ///
+    /// Note that the AUR only searches the main maintainer of packages, so this does not return
+    /// packages which `maintainer` only co-maintains.
+    #[instrument(skip(self))]
+    pub async fn packages_by_maintainer(&self, maintainer: &str) -> Result<Vec<AurPackage>> {
+        let names = self
+            .search(SearchBy::Maintainer, maintainer)
             .await?
-            .error_for_status()?
-            .json()
-            .await?;
-        if info.resultcount != info.results.len() {
-            event!(
-                Level::WARN,
-                "Inconsistent AUR info response: resultcount {} != results.len {}",
-                info.resultcount,
-                info.results.len()
+            .into_iter()
+            .map(|package| package.name)
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            Ok(Vec::new())
+        } else {
+            self.info(&names).await
+        }
+    }
+
+    /// Get information about packages in the given package `bases`.
+    ///
+    /// Unlike [`AurRpcClient::info`], which takes names of packages, this takes names of package
+    /// bases and returns all packages of split package bases.
+    ///
+    /// The AUR RPC interface cannot look up packages by package base, so search packages whose
+    /// name contains the name of the package base, and then get full information about those
+    /// packages which belong to the package base, because search results lack some information,
+    /// e.g. co-maintainers and dependencies.  This does not find members of a package base whose
+    /// names do not contain the name of the package base.
+    #[instrument(skip_all)]
+    pub async fn info_by_base<I, S>(&self, bases: I) -> Result<Vec<AurPackage>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut names = Vec::new();
+        for base in bases {
+            let base = base.as_ref();
+            let packages = self.search(SearchBy::Name, base).await?;
+            names.extend(
+                packages
+                    .into_iter()
+                    .filter(|p| p.package_base == base)
+                    .map(|p| p.name),
             );
         }
-        Ok(info.results)
+        if names.is_empty() {
+            Ok(Vec::new())
+        } else {
+            self.info(&names).await
+        }
+    }
+
+    /// Suggest names of packages which start with `prefix`.
+    ///
+    /// Return package names in the order of the AUR.
+    #[instrument(skip(self))]
+    pub async fn suggest(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut url = self.base_url();
+        url.query_pairs_mut()
+            .append_pair("type", "suggest")
+            .append_pair("arg", prefix);
+        self.get_json(url).await
+    }
+
+    /// Get the JSON response of the given RPC `url`.
+    ///
+    /// Retry transient failures according to [`AurRpcClientBuilder::retries`].
+    async fn get_json<T: DeserializeOwned>(&self, url: reqwest::Url) -> Result<T> {
+        let mut attempt = 0;
+        loop {
+            match self.try_get_json(url.clone()).await {
+                Err(error) if attempt < self.retries && is_transient(&error) => {
+                    attempt += 1;
+                    let delay = match error {
+                        // Wait at least as long as the AUR asks us to
+                        AurRpcError::RateLimited { retry_after } => {
+                            retry_after.max(retry_delay(attempt))
+                        }
+                        _ => retry_delay(attempt),
+                    };
+                    event!(
+                        Level::WARN,
+                        "AUR request failed, retrying in {:?} (attempt {}/{}): {}",
+                        delay,
+                        attempt,
+                        self.retries,
+                        error
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn try_get_json<T: DeserializeOwned>(&self, url: reqwest::Url) -> Result<T> {
+        event!(Level::DEBUG, "GET {}", &url);
+        let response = self.client.get(url).send().await?;
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(AurRpcError::RateLimited {
+                retry_after: retry_after(response.headers()).unwrap_or_default(),
+            });
+        }
+        Ok(response.error_for_status()?.json().await?)
+    }
+}
+
+/// Add the RPC version parameter to `url` unless it already has one.
+fn with_rpc_version(mut url: reqwest::Url) -> reqwest::Url {
+    if !url.query_pairs().any(|(key, _)| key == "v") {
+        url.query_pairs_mut().append_pair("v", RPC_VERSION);
+    }
+    url
+}
+
+/// Whether `error` is transient, i.e. whether to retry the request.
+fn is_transient(error: &AurRpcError) -> bool {
+    match error {
+        AurRpcError::ReqwestError(error) => error.is_connect(),
+        AurRpcError::Http { status, .. } => status.is_server_error(),
+        AurRpcError::RateLimited { .. } => true,
+        AurRpcError::Certificate(_)
+        | AurRpcError::Timeout(_)
+        | AurRpcError::Decode(_)
+        | AurRpcError::UnexpectedResult { .. } => false,
+    }
+}
+
+/// Get the delay from the `Retry-After` header in `headers`, if any.
+///
+/// Only support a delay in seconds, and ignore a HTTP date.
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+    value.trim().parse().ok().map(Duration::from_secs)
+}
+
+/// Get the single result for `package` from `results`.
+fn single_result(package: &str, mut results: Vec<AurPackage>) -> Result<Option<AurPackage>> {
+    if 1 < results.len() {
+        Err(AurRpcError::UnexpectedResult {
+            package: package.to_string(),
+            count: results.len(),
+        })
+    } else {
+        Ok(results.pop())
+    }
+}
+
+/// The delay before the given retry `attempt`, starting at 1.
+///
+/// Double the delay for every attempt, and add random jitter of up to the base delay for the
+/// attempt.
+fn retry_delay(attempt: u32) -> Duration {
+    let base = RETRY_BASE_DELAY * 2u32.saturating_pow(attempt.saturating_sub(1));
+    // RandomState gets new random keys for every instance, which is random enough for jitter.
+    let random = RandomState::new().build_hasher().finish();
+    base + Duration::from_nanos(random % base.as_nanos() as u64)
+}
+
+/// Warn if the `resultcount` reported by the AUR does not match the number of `results`.
+fn warn_inconsistent_resultcount(resultcount: usize, results: &[AurPackage]) {
+    if resultcount != results.len() {
+        event!(
+            Level::WARN,
+            "Inconsistent AUR response: resultcount {} != results.len {}",
+            resultcount,
+            results.len()
+        );
+    }
+}
+
+impl Default for AurRpcClient {
+    /// Create a new default AUR client with [`AurRpcClient::new`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the client fails to build, which only happens if an embedded letsencrypt root
+    /// certificate is invalid.
+    fn default() -> Self {
+        Self::new().expect("Default AUR client should build with pinned configuration")
+    }
+}
+
+/// A source of information about AUR packages.
+///
+/// Abstracts over [`AurRpcClient::info`], to look up packages from other sources, e.g. an
+/// in-memory fake in tests.
+#[async_trait]
+pub trait AurInfoProvider: Sync {
+    /// Get information about all given `packages`.
+    ///
+    /// Omit packages which do not exist.
+    async fn info(&self, packages: &[String]) -> Result<Vec<AurPackage>>;
+
+    /// Get information about a single `package`.
+    ///
+    /// Return `None` if the package does not exist, and fail with
+    /// [`AurRpcError::UnexpectedResult`] if there is more than one package of this name.
+    async fn info_one(&self, package: &str) -> Result<Option<AurPackage>> {
+        single_result(package, self.info(&[package.to_string()]).await?)
+    }
+}
+
+#[async_trait]
+impl AurInfoProvider for AurRpcClient {
+    /// Get information about `packages` with [`AurRpcClient::info`].
+    async fn info(&self, packages: &[String]) -> Result<Vec<AurPackage>> {
+        AurRpcClient::info(self, packages).await
+    }
+}
+
+/// A builder to configure an [`AurRpcClient`].
+///
+/// Accumulate all configuration, and build the client with [`AurRpcClientBuilder::build`].
+#[derive(Debug, Clone)]
+pub struct AurRpcClientBuilder {
+    http_version: HttpVersion,
+    resolve: Vec<(String, SocketAddr)>,
+    proxies: Vec<reqwest::Proxy>,
+    timeout: Duration,
+    connect_timeout: Option<Duration>,
+    pool_idle_timeout: Option<Duration>,
+    min_tls_version: reqwest::tls::Version,
+    system_roots: bool,
+    compression: bool,
+    base_url: reqwest::Url,
+    batch_size: usize,
+    retries: u32,
+    cache_ttl: Option<Duration>,
+}
+
+impl AurRpcClientBuilder {
+    /// Create a new builder with the default configuration of [`AurRpcClient::new`].
+    pub fn new() -> Self {
+        Self {
+            http_version: HttpVersion::default(),
+            resolve: Vec::new(),
+            proxies: Vec::new(),
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: None,
+            pool_idle_timeout: None,
+            min_tls_version: DEFAULT_MIN_TLS_VERSION,
+            system_roots: false,
+            compression: true,
+            base_url: with_rpc_version(
+                reqwest::Url::parse(DEFAULT_BASE_URL)
+                    .expect("Base URL should definitely be valid!"),
+            ),
+            batch_size: DEFAULT_BATCH_SIZE,
+            retries: DEFAULT_RETRIES,
+            cache_ttl: None,
+        }
+    }
+
+    /// Use the given HTTP `version`.
+    pub fn http_version(mut self, version: HttpVersion) -> Self {
+        self.http_version = version;
+        self
+    }
+
+    /// Whether to use HTTP/2 with prior knowledge.
+    ///
+    /// With HTTP/2 all requests multiplex over a single connection to the AUR.  If `enabled` use
+    /// [`HttpVersion::Http2`].  Otherwise negotiate the version with [`HttpVersion::Auto`] if
+    /// HTTP/2 was used, and keep any other version chosen with [`Self::http_version`].
+    pub fn http2_prior_knowledge(self, enabled: bool) -> Self {
+        if enabled {
+            self.http_version(HttpVersion::Http2)
+        } else if self.http_version == HttpVersion::Http2 {
+            self.http_version(HttpVersion::Auto)
+        } else {
+            self
+        }
+    }
+
+    /// Resolve `host` to `addr` instead of using DNS.
+    ///
+    /// Only use the IP address of `addr`, and ignore its port: the client connects to the port of
+    /// the request URL, i.e. the HTTPS port for the AUR, or the port of a custom
+    /// [base URL](Self::base_url).  The client still verifies the TLS certificate against
+    /// `host`, so this does not weaken TLS.
+    pub fn resolve(mut self, host: &str, addr: SocketAddr) -> Self {
+        self.resolve.push((host.to_string(), addr));
+        self
+    }
+
+    /// Send requests through the given `proxy`.
+    ///
+    /// Add multiple proxies to use different proxies for different URLs; the first matching proxy
+    /// wins.  Without any proxy, the client uses the proxies from the standard environment
+    /// variables, e.g. `HTTPS_PROXY`.
+    ///
+    /// Note that the client only trusts the letsencrypt root certificates by default; it cannot
+    /// connect to the AUR through a proxy which intercepts TLS connections with its own
+    /// certificate, unless it uses the system root certificates; see
+    /// `AurRpcClientBuilder::use_system_roots`.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Time out requests after `timeout`.
+    ///
+    /// The timeout applies to the whole request, from connecting to the AUR to reading the
+    /// response body.  A request which times out fails with [`AurRpcError::Timeout`].  Requests
+    /// time out after 30 seconds by default.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Give up connecting to the AUR after `timeout`.
+    ///
+    /// This only limits the time to establish a connection, and does not limit the time for the
+    /// whole request.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Keep idle connections to the AUR open for `timeout`.
+    ///
+    /// The client reuses idle connections for subsequent requests, to avoid a new TLS handshake
+    /// for every request.  By default idle connections stay open for 90 seconds.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Require at least the given TLS `version`.
+    ///
+    /// By default the client requires TLS 1.3, which the AUR supports.  Only lower this for
+    /// mirrors or proxies which do not support TLS 1.3 yet: older TLS versions allow weaker
+    /// cipher suites and expose more of the handshake, and permitting them makes downgrade
+    /// attacks possible against connections which would otherwise use TLS 1.3.
+    pub fn min_tls_version(mut self, version: reqwest::tls::Version) -> Self {
+        self.min_tls_version = version;
+        self
+    }
+
+    /// Trust the root certificates of the system instead of the embedded letsencrypt roots.
+    ///
+    /// By default the client only trusts the letsencrypt root certificates, because the AUR
+    /// uses a letsencrypt certificate.  This pins the AUR to a single certificate authority: no
+    /// other authority can issue a certificate for the AUR which this client would accept.
+    ///
+    /// With system roots the client trusts every certificate authority the system trusts,
+    /// including enterprise authorities of TLS-inspecting firewalls.  Any of these authorities
+    /// can then intercept and alter responses of the AUR, e.g. to hide maintainer changes of
+    /// packages.  Only use system roots if a firewall leaves no other choice.
+    ///
+    /// Requires the `system-roots` feature.
+    #[cfg(feature = "system-roots")]
+    pub fn use_system_roots(mut self) -> Self {
+        self.system_roots = true;
+        self
+    }
+
+    /// Whether to ask for compressed responses.
+    ///
+    /// Compression is enabled by default, and considerably reduces the size of responses about
+    /// many packages.  The client transparently decompresses gzip and deflate responses.
+    pub fn compression(mut self, compression: bool) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Send requests to `base_url` instead of the AUR.
+    ///
+    /// Use this to query an AUR mirror or a local server for testing.  Add the version
+    /// parameter of the AUR RPC interface to `base_url` if it's missing.
+    pub fn base_url(mut self, base_url: reqwest::Url) -> Self {
+        self.base_url = with_rpc_version(base_url);
+        self
+    }
+
+    /// Request information about at most `batch_size` packages at once.
+    ///
+    /// [`AurRpcClient::info`] splits larger requests into multiple requests of at most
+    /// `batch_size` packages each, because the AUR rejects requests with too many packages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is 0.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "Batch size must not be 0");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Retry failed requests at most `retries` times.
+    ///
+    /// Only retry requests which failed to connect, failed with a server error, or were rate
+    /// limited, with exponential backoff between attempts.  After rate limiting wait at least as
+    /// long as the AUR asks to.  Retry 3 times by default.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Cache information about packages for `ttl`.
+    ///
+    /// [`AurRpcClient::info`] and [`AurRpcClient::info_one`] then only request information about
+    /// packages which are not cached, or whose cached information is older than `ttl`.  Clones of
+    /// the client share the cache.  Nothing is cached by default.
+    pub fn cache(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Build the client.
+    pub fn build(self) -> Result<AurRpcClient> {
+        let builder = pinned_client_builder(self.system_roots)?
+            .min_tls_version(self.min_tls_version)
+            .timeout(self.timeout)
+            .gzip(self.compression)
+            .deflate(self.compression);
+        let builder = match self.http_version {
+            HttpVersion::Auto => builder,
+            HttpVersion::Http1 => builder.http1_only(),
+            HttpVersion::Http2 => builder.http2_prior_knowledge(),
+        };
+        let builder = self.resolve.iter().fold(builder, |builder, (host, addr)| {
+            builder.resolve(host, *addr)
+        });
+        let builder = self
+            .proxies
+            .into_iter()
+            .fold(builder, |builder, proxy| builder.proxy(proxy));
+        let builder = match self.connect_timeout {
+            Some(timeout) => builder.connect_timeout(timeout),
+            None => builder,
+        };
+        let builder = match self.pool_idle_timeout {
+            Some(timeout) => builder.pool_idle_timeout(timeout),
+            None => builder,
+        };
+        let client = AurRpcClient {
+            base_url: self.base_url,
+            batch_size: self.batch_size,
+            retries: self.retries,
+            cache: self.cache_ttl.map(|ttl| Arc::new(InfoCache::new(ttl))),
+            ..AurRpcClient::from_client(builder.build()?)
+        };
+        Ok(client)
+    }
+}
+
+impl Default for AurRpcClientBuilder {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::aur::mock::{
+        json_response, keep_alive, serve, serve_connections, serve_delayed, serve_heads,
+        unresponsive,
+    };
     use pretty_assertions::{assert_eq, assert_str_eq};
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn deserialize_info() {
+        let info: AurInfo =
+            serde_json::from_str(include_str!("fixtures/info-1password.json")).unwrap();
+        assert_eq!(info.resultcount, 1);
+        let package = &info.results[0];
+        assert_str_eq!(package.name, "1password");
+        assert_str_eq!(package.package_base, "1password");
+        assert_eq!(package.package_base_id, 139408);
+        assert_eq!(package.out_of_date, None);
+        assert_eq!(package.num_votes, 134);
+        assert_eq!(package.popularity, 3.216174);
+        assert_eq!(package.first_submitted, 1549485209);
+        assert_eq!(package.last_modified, 1669131622);
+        assert_eq!(
+            package.depends,
+            vec!["hicolor-icon-theme", "libsecret", "nss", "gtk3", "libxss"]
+        );
+        assert!(package.make_depends.is_empty());
+        assert_eq!(package.provides, vec!["1password"]);
+    }
+
+    #[test]
+    fn deserialize_dependencies() {
+        let info: AurInfo = serde_json::from_str(include_str!("fixtures/info-paru.json")).unwrap();
+        let package = &info.results[0];
+        assert_str_eq!(package.name, "paru");
+        assert_eq!(package.co_maintainers, vec!["Foxboron"]);
+        assert_eq!(package.depends, vec!["git", "pacman"]);
+        assert_eq!(package.make_depends, vec!["cargo"]);
+        assert!(package.check_depends.is_empty());
+        assert!(package.provides.is_empty());
+    }
+
+    #[test]
+    fn deserialize_split_package() {
+        let info: AurInfo =
+            serde_json::from_str(include_str!("fixtures/info-opencl-nvidia-470xx.json")).unwrap();
+        let package = &info.results[0];
+        assert_str_eq!(package.name, "opencl-nvidia-470xx");
+        assert_str_eq!(package.package_base, "nvidia-470xx-utils");
+        assert_str_eq!(
+            package.git_url().as_str(),
+            "https://aur.archlinux.org/nvidia-470xx-utils.git"
+        );
+    }
+
+    #[test]
+    fn deserialize_orphaned() {
+        let info: AurInfo = serde_json::from_str(
+            &include_str!("fixtures/info-1password.json")
+                .replace(r#""Maintainer":"1Password""#, r#""Maintainer":null"#),
+        )
+        .unwrap();
+        assert_eq!(info.results[0].maintainer, None);
+        let info: AurInfo =
+            serde_json::from_str(include_str!("fixtures/info-1password.json")).unwrap();
+        assert_eq!(info.results[0].maintainer.as_deref(), Some("1Password"));
+    }
+
+    #[test]
+    fn deserialize_keywords() {
+        let info: AurInfo =
+            serde_json::from_str(include_str!("fixtures/info-1password.json")).unwrap();
+        assert_eq!(
+            info.results[0].keywords,
+            vec!["1password", "password", "password-manager"]
+        );
+    }
+
+    #[test]
+    fn deserialize_without_keywords() {
+        let info: AurInfo =
+            serde_json::from_str(&include_str!("fixtures/info-1password.json").replace(
+                r#""Keywords":["1password","password","password-manager"],"#,
+                "",
+            ))
+            .unwrap();
+        assert!(info.results[0].keywords.is_empty());
+    }
+
+    #[test]
+    fn deserialize_out_of_date() {
+        let info: AurInfo = serde_json::from_str(
+            &include_str!("fixtures/info-1password.json")
+                .replace(r#""OutOfDate":null"#, r#""OutOfDate":1670000000"#),
+        )
+        .unwrap();
+        assert_eq!(info.results[0].out_of_date, Some(1670000000));
+    }
+
+    #[test]
+    fn retry_delay_backoff() {
+        for attempt in 1..=5 {
+            let base = RETRY_BASE_DELAY * 2u32.pow(attempt - 1);
+            let delay = retry_delay(attempt);
+            assert!(base <= delay && delay < base * 2, "{:?}", delay);
+        }
+    }
+
+    #[test]
+    fn single_result_many() {
+        let info: AurInfo =
+            serde_json::from_str(include_str!("fixtures/info-1password.json")).unwrap();
+        let package = info.results[0].clone();
+        assert!(single_result("1password", vec![]).unwrap().is_none());
+        assert_str_eq!(
+            single_result("1password", vec![package.clone()])
+                .unwrap()
+                .unwrap()
+                .name,
+            "1password"
+        );
+        match single_result("1password", vec![package.clone(), package]).unwrap_err() {
+            AurRpcError::UnexpectedResult { package, count } => {
+                assert_str_eq!(package, "1password");
+                assert_eq!(count, 2);
+            }
+            other => panic!("Unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn default_base_url() {
+        assert_str_eq!(
+            AurRpcClient::default().base_url().as_str(),
+            "https://aur.archlinux.org/rpc/?v=5"
+        );
+    }
+
+    #[test]
+    fn base_url_adds_version() {
+        let client =
+            AurRpcClient::with_base_url("http://localhost:8080/rpc".parse().unwrap()).unwrap();
+        assert_str_eq!(client.base_url().as_str(), "http://localhost:8080/rpc?v=5");
+        let client =
+            AurRpcClient::with_base_url("http://localhost:8080/rpc?v=6".parse().unwrap()).unwrap();
+        assert_str_eq!(client.base_url().as_str(), "http://localhost:8080/rpc?v=6");
+    }
+
+    #[tokio::test]
+    async fn info_from_base_url() {
+        let (url, requests) =
+            serve(|_| json_response("200 OK", include_str!("fixtures/info-1password.json"))).await;
+        let results = AurRpcClient::with_base_url(url)
+            .unwrap()
+            .info(&["1password"])
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_str_eq!(results[0].name, "1password");
+        assert_eq!(
+            *requests.lock().unwrap(),
+            ["/rpc/?v=5&type=info&arg%5B%5D=1password"]
+        );
+    }
+
+    #[test]
+    fn default_client() {
+        AurRpcClient::default();
+    }
 
     #[tokio::test]
     async fn single_get_single_maintainer() {
@@ -141,7 +1118,7 @@ mod test {
             .unwrap();
         assert_eq!(results.len(), 1);
         assert_str_eq!(results[0].name, "1password");
-        assert_str_eq!(results[0].maintainer, "1Password");
+        assert_eq!(results[0].maintainer.as_deref(), Some("1Password"));
         assert!(
             results[0].co_maintainers.is_empty(),
             "Maintainers: {:?}",
@@ -158,13 +1135,667 @@ mod test {
             .unwrap();
         assert_eq!(results.len(), 1);
         assert_str_eq!(results[0].name, "aurutils");
-        assert_str_eq!(results[0].maintainer, "Alad");
+        assert_eq!(results[0].maintainer.as_deref(), Some("Alad"));
         assert_eq!(
             results[0].co_maintainers,
             vec!["cgirard", "maximbaz", "rafasc"]
         );
     }
 
+    #[tokio::test]
+    async fn info_one_existing() {
+        let package = AurRpcClient::new()
+            .unwrap()
+            .info_one("1password")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_str_eq!(package.name, "1password");
+    }
+
+    #[tokio::test]
+    async fn info_one_nonexistent() {
+        let package = AurRpcClient::new()
+            .unwrap()
+            .info_one("aur-trust-does-not-exist")
+            .await
+            .unwrap();
+        assert!(package.is_none(), "{:?}", package);
+    }
+
+    #[tokio::test]
+    async fn single_get_http1() {
+        let (url, heads, _) =
+            serve_heads(|_| json_response("200 OK", include_str!("fixtures/info-1password.json")))
+                .await;
+        let results = AurRpcClient::builder()
+            .base_url(url)
+            .http_version(HttpVersion::Http1)
+            .build()
+            .unwrap()
+            .info(&["1password"])
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_str_eq!(results[0].name, "1password");
+        let heads = heads.lock().unwrap();
+        assert_eq!(heads.len(), 1);
+        let request_line = heads[0].lines().next().unwrap();
+        assert!(request_line.ends_with(" HTTP/1.1"), "{}", request_line);
+    }
+
+    #[tokio::test]
+    async fn resolve_override() {
+        let (url, requests) =
+            serve(|_| json_response("200 OK", include_str!("fixtures/info-1password.json"))).await;
+        let addr = SocketAddr::new([127, 0, 0, 1].into(), url.port().unwrap());
+        // The resolved address has no port, so the URL needs the port of the mock server
+        let aur_url = format!("http://aur.archlinux.org:{}/rpc/", addr.port())
+            .parse()
+            .unwrap();
+        let results = AurRpcClient::builder()
+            .base_url(aur_url)
+            .resolve("aur.archlinux.org", addr)
+            .build()
+            .unwrap()
+            .info(&["1password"])
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_str_eq!(results[0].name, "1password");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn timeout() {
+        let (url, heads) = serve_delayed(Duration::from_secs(2), |_| {
+            json_response("200 OK", include_str!("fixtures/info-1password.json"))
+        })
+        .await;
+        let error = AurRpcClient::builder()
+            .base_url(url)
+            .timeout(Duration::from_millis(100))
+            .build()
+            .unwrap()
+            .info(&["1password"])
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AurRpcError::Timeout(_)), "{:?}", error);
+        assert_eq!(heads.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn connect_timeout() {
+        let unresponsive = unresponsive().await;
+        let timeout = Duration::from_millis(200);
+        let start = std::time::Instant::now();
+        let error = AurRpcClient::builder()
+            .base_url(unresponsive.url.clone())
+            .connect_timeout(timeout)
+            .build()
+            .unwrap()
+            .info(&["1password"])
+            .await
+            .unwrap_err();
+        let elapsed = start.elapsed();
+        match error {
+            AurRpcError::Timeout(error) => assert!(error.is_timeout(), "{:?}", error),
+            other => panic!("Unexpected error: {:?}", other),
+        }
+        assert!(timeout <= elapsed, "{:?}", elapsed);
+        assert!(elapsed < timeout + Duration::from_secs(1), "{:?}", elapsed);
+    }
+
+    #[tokio::test]
+    async fn rate_limited_then_ok() {
+        let mut rate_limited = false;
+        let (url, requests) = serve(move |_| {
+            if rate_limited {
+                json_response("200 OK", include_str!("fixtures/info-1password.json"))
+            } else {
+                rate_limited = true;
+                "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 1\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+            }
+        })
+        .await;
+        let start = std::time::Instant::now();
+        let results = AurRpcClient::with_base_url(url)
+            .unwrap()
+            .info(&["1password"])
+            .await
+            .unwrap();
+        assert!(Duration::from_secs(1) <= start.elapsed());
+        assert_eq!(results.len(), 1);
+        assert_str_eq!(results[0].name, "1password");
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn retry_server_error_then_ok() {
+        let mut failed = false;
+        let (url, requests) = serve(move |_| {
+            if failed {
+                json_response("200 OK", include_str!("fixtures/info-1password.json"))
+            } else {
+                failed = true;
+                json_response("503 Service Unavailable", "{}")
+            }
+        })
+        .await;
+        let results = AurRpcClient::builder()
+            .base_url(url)
+            .retries(3)
+            .build()
+            .unwrap()
+            .info(&["1password"])
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_str_eq!(results[0].name, "1password");
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn no_retry_on_client_error() {
+        let (url, requests) = serve(|_| json_response("404 Not Found", "{}")).await;
+        let error = AurRpcClient::builder()
+            .base_url(url)
+            .retries(3)
+            .build()
+            .unwrap()
+            .info(&["1password"])
+            .await
+            .unwrap_err();
+        match error {
+            AurRpcError::Http { status, .. } => assert_eq!(status, reqwest::StatusCode::NOT_FOUND),
+            other => panic!("Unexpected error: {:?}", other),
+        }
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn retry_server_error_until_retries_exhausted() {
+        let mut statuses = [
+            "500 Internal Server Error",
+            "502 Bad Gateway",
+            "503 Service Unavailable",
+        ]
+        .into_iter();
+        let (url, requests) =
+            serve(move |_| json_response(statuses.next().unwrap_or("200 OK"), "{}")).await;
+        let error = AurRpcClient::builder()
+            .base_url(url)
+            .retries(2)
+            .build()
+            .unwrap()
+            .info(&["1password"])
+            .await
+            .unwrap_err();
+        match error {
+            AurRpcError::Http { status, .. } => {
+                assert_eq!(status, reqwest::StatusCode::SERVICE_UNAVAILABLE)
+            }
+            other => panic!("Unexpected error: {:?}", other),
+        }
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn rate_limited_without_retries() {
+        let (url, _) = serve(|_| {
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 120\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+        })
+        .await;
+        let error = AurRpcClient::builder()
+            .base_url(url)
+            .retries(0)
+            .build()
+            .unwrap()
+            .info(&["1password"])
+            .await
+            .unwrap_err();
+        match error {
+            AurRpcError::RateLimited { retry_after } => {
+                assert_eq!(retry_after, Duration::from_secs(120))
+            }
+            other => panic!("Unexpected error: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn gzip_response() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(include_bytes!("fixtures/info-1password.json"))
+            .unwrap();
+        let body = encoder.finish().unwrap();
+        let (url, _) = serve(move |_| {
+            let mut response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            )
+            .into_bytes();
+            response.extend_from_slice(&body);
+            response
+        })
+        .await;
+        let results = AurRpcClient::with_base_url(url)
+            .unwrap()
+            .info(&["1password"])
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_str_eq!(results[0].name, "1password");
+    }
+
+    #[test]
+    fn without_compression() {
+        AurRpcClient::with_compression(false).unwrap();
+    }
+
+    #[tokio::test]
+    async fn info_one_cached() {
+        let (url, requests) =
+            serve(|_| json_response("200 OK", include_str!("fixtures/info-1password.json"))).await;
+        let client = AurRpcClient::builder()
+            .base_url(url)
+            .cache(Duration::from_secs(60))
+            .build()
+            .unwrap();
+        for _ in 0..2 {
+            let package = client.info_one("1password").await.unwrap().unwrap();
+            assert_str_eq!(package.name, "1password");
+        }
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn info_cache_expires() {
+        let (url, requests) =
+            serve(|_| json_response("200 OK", include_str!("fixtures/info-1password.json"))).await;
+        let client = AurRpcClient::builder()
+            .base_url(url)
+            .cache(Duration::ZERO)
+            .build()
+            .unwrap();
+        for _ in 0..2 {
+            client.info_one("1password").await.unwrap().unwrap();
+        }
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn reuse_connection() {
+        let (url, requests, connections) = serve_connections(|_| {
+            keep_alive(json_response(
+                "200 OK",
+                include_str!("fixtures/info-1password.json"),
+            ))
+        })
+        .await;
+        let client = AurRpcClient::builder()
+            .base_url(url)
+            .pool_idle_timeout(Duration::from_secs(60))
+            .build()
+            .unwrap();
+        for _ in 0..5 {
+            client.info(&["1password"]).await.unwrap();
+        }
+        assert_eq!(requests.lock().unwrap().len(), 5);
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn letsencrypt_roots_load() {
+        assert_eq!(letsencrypt_roots().unwrap().len(), 2);
+        assert!(AurRpcClient::new().is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "system-roots")]
+    fn builder_system_roots() {
+        let builder = AurRpcClient::builder().use_system_roots();
+        assert!(builder.system_roots);
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn builder_min_tls_version() {
+        let builder = AurRpcClient::builder();
+        assert_eq!(builder.min_tls_version, reqwest::tls::Version::TLS_1_3);
+        let builder = builder.min_tls_version(reqwest::tls::Version::TLS_1_2);
+        assert_eq!(builder.min_tls_version, reqwest::tls::Version::TLS_1_2);
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn builder_http2_prior_knowledge() {
+        let builder = AurRpcClient::builder().http2_prior_knowledge(true);
+        assert_eq!(builder.http_version, HttpVersion::Http2);
+        let builder = builder.http2_prior_knowledge(false);
+        assert_eq!(builder.http_version, HttpVersion::Auto);
+    }
+
+    #[test]
+    fn builder_http2_prior_knowledge_disabled_keeps_http1() {
+        let builder = AurRpcClient::builder()
+            .http_version(HttpVersion::Http1)
+            .http2_prior_knowledge(false);
+        assert_eq!(builder.http_version, HttpVersion::Http1);
+    }
+
+    #[tokio::test]
+    async fn builder_options() {
+        let (url, requests) = serve(|target| {
+            let body = if target.ends_with("arg%5B%5D=1password") {
+                include_str!("fixtures/info-1password.json")
+            } else {
+                include_str!("fixtures/info-paru.json")
+            };
+            json_response("200 OK", body)
+        })
+        .await;
+        let client = AurRpcClient::builder()
+            .base_url(url)
+            .http_version(HttpVersion::Http1)
+            .timeout(Duration::from_secs(5))
+            .compression(false)
+            .batch_size(1)
+            .retries(0)
+            .cache(Duration::from_secs(60))
+            .build()
+            .unwrap();
+        let results = client.info(&["1password", "paru"]).await.unwrap();
+        assert_eq!(
+            results.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(),
+            ["1password", "paru"]
+        );
+        let results = client.info(&["1password", "paru"]).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                "/rpc/?v=5&type=info&arg%5B%5D=1password",
+                "/rpc/?v=5&type=info&arg%5B%5D=paru"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn proxy() {
+        let (proxy_url, requests) =
+            serve(|_| json_response("200 OK", include_str!("fixtures/info-1password.json"))).await;
+        let client = AurRpcClient::builder()
+            .base_url("http://aur.invalid/rpc/".parse().unwrap())
+            .proxy(reqwest::Proxy::http(proxy_url).unwrap())
+            .build()
+            .unwrap();
+        let package = client.info_one("1password").await.unwrap().unwrap();
+        assert_str_eq!(package.name, "1password");
+        assert_eq!(
+            *requests.lock().unwrap(),
+            ["http://aur.invalid/rpc/?v=5&type=info&arg%5B%5D=1password"]
+        );
+    }
+
+    #[tokio::test]
+    async fn http_error_status() {
+        let (url, _) = serve(|_| json_response("404 Not Found", "{}")).await;
+        let error = AurRpcClient::with_base_url(url)
+            .unwrap()
+            .info(&["1password"])
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, AurRpcError::Http { status, .. } if status == reqwest::StatusCode::NOT_FOUND),
+            "{:?}",
+            error
+        );
+        assert_str_eq!(
+            error.to_string(),
+            "AUR responded with HTTP status 404 Not Found"
+        );
+        let source = std::error::Error::source(&error).unwrap().to_string();
+        assert!(source.contains("404 Not Found"), "{}", source);
+    }
+
+    #[tokio::test]
+    async fn decode_error() {
+        let (url, _) = serve(|_| json_response("200 OK", r#"{"results":"#)).await;
+        let error = AurRpcClient::with_base_url(url)
+            .unwrap()
+            .info(&["1password"])
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AurRpcError::Decode(_)), "{:?}", error);
+    }
+
+    #[tokio::test]
+    async fn info_without_packages() {
+        let (url, requests) =
+            serve(|_| json_response("200 OK", include_str!("fixtures/info-1password.json"))).await;
+        let client = AurRpcClient::with_base_url(url).unwrap();
+        let results = client.info(&[] as &[&str]).await.unwrap();
+        assert!(results.is_empty());
+        assert!(requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn info_with_count_matches_results() {
+        let (url, _) =
+            serve(|_| json_response("200 OK", include_str!("fixtures/info-1password.json"))).await;
+        let client = AurRpcClient::with_base_url(url).unwrap();
+        let (count, results) = client.info_with_count(&["1password"]).await.unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(count, results.len());
+    }
+
+    #[tokio::test]
+    async fn info_with_count_inconsistent() {
+        let (url, _) = serve(|_| {
+            json_response(
+                "200 OK",
+                &include_str!("fixtures/info-1password.json")
+                    .replace(r#""resultcount":1"#, r#""resultcount":2"#),
+            )
+        })
+        .await;
+        let client = AurRpcClient::with_base_url(url).unwrap();
+        let (count, results) = client.info_with_count(&["1password"]).await.unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(results.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn packages_by_maintainer_search_and_info() {
+        let (url, requests) = serve(|target| {
+            let package = |name: &str| {
+                serde_json::json!({
+                    "Name": name,
+                    "PackageBase": name,
+                    "PackageBaseID": 1,
+                    "OutOfDate": null,
+                    "NumVotes": 1,
+                    "Popularity": 0.0,
+                    "FirstSubmitted": 0,
+                    "LastModified": 0,
+                    "Maintainer": "swsnr",
+                    "CoMaintainers": ["foo"],
+                })
+            };
+            let results = [
+                "dracut-hook-uefi",
+                "gnome-search-providers-jetbrains",
+                "mdcat",
+            ]
+            .into_iter()
+            .filter(|name| target.contains("type=search") || target.contains(name))
+            .map(package)
+            .collect::<Vec<_>>();
+            let body = serde_json::json!({"resultcount": results.len(), "results": results});
+            json_response("200 OK", &body.to_string())
+        })
+        .await;
+        let client = AurRpcClient::with_base_url(url).unwrap();
+        let results = client.packages_by_maintainer("swsnr").await.unwrap();
+        assert_eq!(
+            results.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(),
+            [
+                "dracut-hook-uefi",
+                "gnome-search-providers-jetbrains",
+                "mdcat"
+            ]
+        );
+        assert!(results.iter().all(|p| p.co_maintainers == ["foo"]));
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                "/rpc/?v=5&type=search&by=maintainer&arg=swsnr",
+                "/rpc/?v=5&type=info&arg%5B%5D=dracut-hook-uefi&arg%5B%5D=gnome-search-providers-jetbrains&arg%5B%5D=mdcat"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn packages_by_unknown_maintainer() {
+        let (url, requests) =
+            serve(|_| json_response("200 OK", r#"{"resultcount":0,"results":[]}"#)).await;
+        let client = AurRpcClient::with_base_url(url).unwrap();
+        assert!(client
+            .packages_by_maintainer("aur-trust-does-not-exist")
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn info_by_base_split_package() {
+        let (url, requests) = serve(|target| {
+            // Like the AUR, only return co-maintainers in info results, not in search results
+            let info = target.contains("type=info");
+            let url = reqwest::Url::parse(&format!("http://localhost{}", target)).unwrap();
+            let names = url
+                .query_pairs()
+                .filter(|(key, _)| key == "arg[]")
+                .map(|(_, name)| name.into_owned())
+                .collect::<Vec<_>>();
+            let package = |name: &str, base: &str| {
+                let mut package = serde_json::json!({
+                    "Name": name,
+                    "PackageBase": base,
+                    "PackageBaseID": 1,
+                    "OutOfDate": null,
+                    "NumVotes": 1,
+                    "Popularity": 0.0,
+                    "FirstSubmitted": 0,
+                    "LastModified": 0,
+                    "Maintainer": "jonathon",
+                });
+                if info {
+                    package["CoMaintainers"] = serde_json::json!(["foo"]);
+                }
+                package
+            };
+            let results = [
+                package("nvidia-470xx-utils", "nvidia-470xx-utils"),
+                package("lib32-nvidia-470xx-utils", "lib32-nvidia-470xx-utils"),
+                package("nvidia-470xx-utils-dkms", "nvidia-470xx-utils"),
+            ]
+            .into_iter()
+            .filter(|package| !info || names.iter().any(|name| package["Name"] == *name))
+            .collect::<Vec<_>>();
+            let body = serde_json::json!({"resultcount": results.len(), "results": results});
+            json_response("200 OK", &body.to_string())
+        })
+        .await;
+        let results = AurRpcClient::with_base_url(url)
+            .unwrap()
+            .info_by_base(&["nvidia-470xx-utils"])
+            .await
+            .unwrap();
+        assert_eq!(
+            results.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(),
+            ["nvidia-470xx-utils", "nvidia-470xx-utils-dkms"]
+        );
+        assert!(results.iter().all(|p| p.co_maintainers == ["foo"]));
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                "/rpc/?v=5&type=search&by=name&arg=nvidia-470xx-utils",
+                "/rpc/?v=5&type=info&arg%5B%5D=nvidia-470xx-utils&arg%5B%5D=nvidia-470xx-utils-dkms"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn suggest_from_base_url() {
+        let (url, requests) =
+            serve(|_| json_response("200 OK", r#"["firefox","firefox-beta-bin","firejail-git"]"#))
+                .await;
+        let suggestions = AurRpcClient::with_base_url(url)
+            .unwrap()
+            .suggest("fire")
+            .await
+            .unwrap();
+        assert_eq!(suggestions, ["firefox", "firefox-beta-bin", "firejail-git"]);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            ["/rpc/?v=5&type=suggest&arg=fire"]
+        );
+    }
+
+    #[tokio::test]
+    async fn suggest() {
+        let suggestions = AurRpcClient::new().unwrap().suggest("fire").await.unwrap();
+        assert!(
+            suggestions.iter().any(|name| name.starts_with("firefox")),
+            "Suggestions: {:?}",
+            suggestions
+        );
+    }
+
+    #[tokio::test]
+    async fn search_by_maintainer() {
+        let results = AurRpcClient::new()
+            .unwrap()
+            .search(SearchBy::Maintainer, "swsnr")
+            .await
+            .unwrap();
+        assert!(
+            results.iter().any(|p| p.name == "dracut-hook-uefi"),
+            "Results: {:?}",
+            results
+        );
+    }
+
+    #[tokio::test]
+    async fn multiget_many() {
+        let names = std::iter::once("1password".to_string())
+            .chain((1..500).map(|i| format!("aur-trust-does-not-exist-{}", i)))
+            .collect::<Vec<_>>();
+        let results = AurRpcClient::new().unwrap().info(&names).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_str_eq!(results[0].name, "1password");
+    }
+
+    #[tokio::test]
+    async fn multiget_batches() {
+        let results = AurRpcClient::builder()
+            .batch_size(1)
+            .build()
+            .unwrap()
+            .info(&["1password", "dracut-hook-uefi"])
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_str_eq!(results[0].name, "1password");
+        assert_str_eq!(results[1].name, "dracut-hook-uefi");
+    }
+
     #[tokio::test]
     async fn multiget() {
         let results = AurRpcClient::new()
@@ -174,8 +1805,8 @@ mod test {
             .unwrap();
         assert_eq!(results.len(), 2);
         assert_str_eq!(results[0].name, "1password");
-        assert_str_eq!(results[0].maintainer, "1Password");
+        assert_eq!(results[0].maintainer.as_deref(), Some("1Password"));
         assert_str_eq!(results[1].name, "dracut-hook-uefi");
-        assert_str_eq!(results[1].maintainer, "swsnr");
+        assert_eq!(results[1].maintainer.as_deref(), Some("swsnr"));
     }
 }
@@ -0,0 +1,688 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Gather trust evidence from git repositories.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use thiserror::Error;
+
+use crate::trust::{CommitSignature, GitCommit, Sha1AbbrevError, SignatureValidity};
+
+/// Reading evidence from a git repository failed.
+#[derive(Error, Debug)]
+pub enum GitError {
+    /// Running git failed.
+    #[error("failed to run git")]
+    Io(#[from] std::io::Error),
+    /// Git exited with an error.
+    #[error("git failed with {status}: {stderr}")]
+    Failed {
+        /// The exit status of git.
+        status: std::process::ExitStatus,
+        /// What git printed to stderr.
+        stderr: String,
+    },
+    /// Git returned an invalid commit ID.
+    #[error("git returned an invalid commit ID")]
+    InvalidSha1(#[from] Sha1AbbrevError),
+    /// Git printed output about the signature of a commit which we could not parse.
+    #[error("failed to parse signature verification output: {0}")]
+    UnknownSignature(String),
+}
+
+/// Run git with `args` in the repository at `repo_path`.
+fn git(repo_path: &Path, args: &[&str]) -> Result<Output, GitError> {
+    Ok(Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(args)
+        .output()?)
+}
+
+/// Read the HEAD commit of the git repository at `repo_path`, e.g. a clone of an AUR package.
+///
+/// Take the SHA1 of HEAD from `git log`, and the signature of the commit with this SHA1 from
+/// `git verify-commit --raw`; see [`parse_verify_commit`].  Verify signatures with the GPG
+/// keyring of the current user, or the allowed signers file configured for the repository for
+/// SSH signatures.
+///
+/// Only read objects, and never look at a work tree, so `repo_path` may also be a bare or a
+/// mirror clone.
+///
+/// Fail with [`GitError::UnknownSignature`] if `git verify-commit` prints output which is not
+/// a signature we understand, rather than treat the commit as unsigned.
+pub fn head_commit_from_repo(repo_path: &Path) -> Result<GitCommit, GitError> {
+    let log = git(repo_path, &["log", "-1", "--format=%H %h"])?;
+    if !log.status.success() {
+        return Err(GitError::Failed {
+            status: log.status,
+            stderr: String::from_utf8_lossy(&log.stderr).trim().to_string(),
+        });
+    }
+    let log = String::from_utf8_lossy(&log.stdout);
+    let (sha1, abbrev_sha1) = log.trim().split_once(' ').unwrap_or_default();
+    let abbrev_sha1 = abbrev_sha1.try_into()?;
+    // Verify the commit we just read, in case HEAD moved in the meantime.  verify-commit fails
+    // for unsigned commits and bad signatures alike, so ignore its status and look at its output
+    // only.
+    let verify = git(repo_path, &["verify-commit", "--raw", sha1])?;
+    let output = String::from_utf8_lossy(&verify.stderr);
+    match parse_verify_commit(&output) {
+        // git prints nothing for unsigned commits
+        None if !output.trim().is_empty() => {
+            Err(GitError::UnknownSignature(output.trim().to_string()))
+        }
+        signature => Ok(GitCommit::new(abbrev_sha1, signature)),
+    }
+}
+
+/// The prefix of GPG status lines.
+static STATUS_PREFIX: &str = "[GNUPG:] ";
+
+/// The name of the OpenPGP public key algorithm with the given `id`.
+///
+/// Return the numeric `id` itself for unknown algorithms.
+fn public_key_algorithm(id: &str) -> String {
+    match id {
+        "1" | "2" | "3" => "RSA",
+        "16" => "ElGamal",
+        "17" => "DSA",
+        "18" => "ECDH",
+        "19" => "ECDSA",
+        "22" => "EdDSA",
+        other => other,
+    }
+    .to_string()
+}
+
+/// Parse the signature of a commit from the output of `git verify-commit --raw`.
+///
+/// `status_output` holds the GPG status lines which `git verify-commit --raw` prints to stderr.
+/// Map these lines to a [`CommitSignature`] the same way git itself computes `%G?`; in
+/// particular a good signature only has [`SignatureValidity::Good`] if GPG trusts the key at
+/// least marginally, and [`SignatureValidity::UnknownValidity`] otherwise.  A signature which
+/// GPG could not check at all, e.g. because the key is missing, also has unknown validity.
+///
+/// Take the fingerprint of the primary key, the key algorithm and the signature timestamp from
+/// the `VALIDSIG` line, or from the `ERRSIG` line for signatures which GPG could not check, and
+/// the expiry of the signing key from the `KEYEXPIRED` line, if any.
+///
+/// If `status_output` has no GPG status lines, parse it as output of SSH signature
+/// verification by `ssh-keygen` instead.
+///
+/// Return `None` if `status_output` contains no signature.
+pub fn parse_verify_commit(status_output: &str) -> Option<CommitSignature> {
+    let mut signature: Option<CommitSignature> = None;
+    let mut fingerprint: Option<String> = None;
+    let mut algorithm: Option<String> = None;
+    let mut key_expiry: Option<i64> = None;
+    let mut sig_timestamp: Option<i64> = None;
+    let mut trusted = false;
+    for line in status_output.lines() {
+        let Some(status) = line.strip_prefix(STATUS_PREFIX) else {
+            continue;
+        };
+        let (keyword, args) = status.split_once(' ').unwrap_or((status, ""));
+        let validity = match keyword {
+            "GOODSIG" => SignatureValidity::Good,
+            "BADSIG" => SignatureValidity::BadSignature,
+            "EXPSIG" => SignatureValidity::ExpiredSignature,
+            "EXPKEYSIG" => SignatureValidity::ExpiredKey,
+            "REVKEYSIG" => SignatureValidity::RevokedKey,
+            "ERRSIG" => {
+                // <keyid> <pkalgo> <hashalgo> <sig_class> <time> <rc> [<fpr>]
+                let fields: Vec<&str> = args.split(' ').collect();
+                algorithm = fields.get(1).map(|id| public_key_algorithm(id));
+                sig_timestamp = fields.get(4).and_then(|time| time.parse().ok());
+                fingerprint = fields
+                    .get(6)
+                    .filter(|fpr| !fpr.is_empty())
+                    .map(|fpr| fpr.to_string());
+                signature = Some(CommitSignature {
+                    validity: SignatureValidity::UnknownValidity,
+                    signer: "unknown signer".into(),
+                    key_id: fields[0].into(),
+                    fingerprint: None,
+                    algorithm: None,
+                    key_expiry: None,
+                    sig_timestamp: None,
+                });
+                continue;
+            }
+            "VALIDSIG" => {
+                // <fpr> <date> <timestamp> <expire> <version> <reserved> <pkalgo> <hashalgo>
+                // <sig_class> [<primary_key_fpr>]
+                let fields: Vec<&str> = args.split(' ').collect();
+                algorithm = fields.get(6).map(|id| public_key_algorithm(id));
+                // GPG may print the timestamp in ISO 8601 format; only take unix timestamps
+                sig_timestamp = fields.get(2).and_then(|time| time.parse().ok());
+                fingerprint = fields
+                    .get(9)
+                    .or(fields.first())
+                    .filter(|fpr| !fpr.is_empty())
+                    .map(|fpr| fpr.to_string());
+                continue;
+            }
+            "KEYEXPIRED" => {
+                // <expire-timestamp>
+                key_expiry = args.parse().ok();
+                continue;
+            }
+            "TRUST_MARGINAL" | "TRUST_FULLY" | "TRUST_ULTIMATE" => {
+                trusted = true;
+                continue;
+            }
+            _ => continue,
+        };
+        let (key_id, signer) = args.split_once(' ').unwrap_or((args, ""));
+        signature = Some(CommitSignature {
+            validity,
+            signer: signer.into(),
+            key_id: key_id.into(),
+            fingerprint: None,
+            algorithm: None,
+            key_expiry: None,
+            sig_timestamp: None,
+        });
+    }
+    signature
+        .map(|signature| {
+            let validity = if signature.validity == SignatureValidity::Good && !trusted {
+                SignatureValidity::UnknownValidity
+            } else {
+                signature.validity
+            };
+            CommitSignature {
+                validity,
+                fingerprint,
+                algorithm,
+                key_expiry,
+                sig_timestamp,
+                ..signature
+            }
+        })
+        .or_else(|| parse_ssh_verify_output(status_output))
+}
+
+/// Parse the SSH signature of a commit from the output of `git verify-commit --raw`.
+///
+/// For SSH signatures `git verify-commit --raw` prints the output of `ssh-keygen -Y verify`.
+/// Like git, map `Good "git" signature for <principal> with <type> key <fingerprint>` to a good
+/// signature by `<principal>`, and a good signature without principal, i.e. by a key not listed
+/// in the allowed signers file, to a signature of unknown validity.  A revoked key makes
+/// `ssh-keygen` fail with `Key is revoked`; any other failure to verify the signature is a bad
+/// signature.
+///
+/// The signing key of SSH signatures is the SHA256 fingerprint of the key, as both key ID and
+/// fingerprint, with algorithm `ssh`.
+///
+/// Return `None` if `output` contains no SSH signature.
+fn parse_ssh_verify_output(output: &str) -> Option<CommitSignature> {
+    let signature = |validity, signer: &str, key: &str| CommitSignature {
+        validity,
+        signer: signer.into(),
+        key_id: key.into(),
+        fingerprint: Some(key.into()),
+        algorithm: Some("ssh".into()),
+        key_expiry: None,
+        sig_timestamp: None,
+    };
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix(r#"Good "git" signature "#) {
+            let (_, key) = rest.rsplit_once(" key ")?;
+            return match rest.strip_prefix("for ") {
+                Some(principal) => {
+                    let (principal, _) = principal.rsplit_once(" with ")?;
+                    Some(signature(SignatureValidity::Good, principal, key))
+                }
+                None => Some(signature(
+                    SignatureValidity::UnknownValidity,
+                    "unknown signer",
+                    key,
+                )),
+            };
+        }
+    }
+    let key = output
+        .split_whitespace()
+        .find(|word| word.starts_with("SHA256:"))
+        .unwrap_or("unknown key");
+    if output.lines().any(|line| line.ends_with("Key is revoked")) {
+        Some(signature(
+            SignatureValidity::RevokedKey,
+            "unknown signer",
+            key,
+        ))
+    } else if output
+        .lines()
+        .any(|line| line == "Could not verify signature.")
+    {
+        Some(signature(
+            SignatureValidity::BadSignature,
+            "unknown signer",
+            key,
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::trust::{check_commit_signature, Trust, TrustDatabase};
+    use pretty_assertions::assert_eq;
+    use quickcheck_macros::quickcheck;
+    use std::path::PathBuf;
+
+    /// A temporary git repository, deleted on drop.
+    struct FixtureRepo(PathBuf);
+
+    impl FixtureRepo {
+        /// Create a new repository with an SSH signing key for Jane Doe.
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "aur-trust-test-{}-{}",
+                name,
+                std::process::id()
+            ));
+            // Clean up leftovers of an earlier run
+            let _ = std::fs::remove_dir_all(&path);
+            std::fs::create_dir_all(&path).unwrap();
+            let repo = Self(path);
+            repo.run("git", &["init", "--quiet", "."]);
+            repo.run(
+                "ssh-keygen",
+                &["-q", "-t", "ed25519", "-N", "", "-C", "jane", "-f", "key"],
+            );
+            let public_key = std::fs::read_to_string(repo.0.join("key.pub")).unwrap();
+            std::fs::write(
+                repo.0.join("allowed_signers"),
+                format!("jane@example.com {}", public_key),
+            )
+            .unwrap();
+            let key = repo.0.join("key");
+            let allowed_signers = repo.0.join("allowed_signers");
+            for (key, value) in [
+                ("user.name", "Jane Doe"),
+                ("user.email", "jane@example.com"),
+                ("gpg.format", "ssh"),
+                ("user.signingkey", key.to_str().unwrap()),
+                (
+                    "gpg.ssh.allowedSignersFile",
+                    allowed_signers.to_str().unwrap(),
+                ),
+            ] {
+                repo.run("git", &["config", key, value]);
+            }
+            repo
+        }
+
+        fn run(&self, program: &str, args: &[&str]) {
+            let output = Command::new(program)
+                .args(args)
+                .current_dir(&self.0)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{:?}", output);
+        }
+
+        fn commit(&self, sign: bool) -> String {
+            let sign = if sign { "-S" } else { "--no-gpg-sign" };
+            self.run(
+                "git",
+                &["commit", "--quiet", "--allow-empty", "-m", "Commit", sign],
+            );
+            let output = git(&self.0, &["rev-parse", "HEAD"]).unwrap();
+            String::from_utf8(output.stdout).unwrap().trim().to_string()
+        }
+    }
+
+    impl Drop for FixtureRepo {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn head_commit_from_repo_signed() {
+        let repo = FixtureRepo::new("signed");
+        let sha1 = repo.commit(true);
+        let commit = head_commit_from_repo(&repo.0).unwrap();
+        assert!(sha1.starts_with(commit.sha1()));
+        let signature = commit.signature().unwrap();
+        assert_eq!(signature.validity, SignatureValidity::Good);
+        assert_eq!(signature.signer, "jane@example.com");
+        assert_eq!(signature.algorithm.as_deref(), Some("ssh"));
+        let trustdb = TrustDatabase::new().trust_key(signature.key().to_string());
+        assert_eq!(
+            check_commit_signature(&trustdb, &commit).trust(),
+            Trust::Trusted
+        );
+    }
+
+    #[test]
+    fn head_commit_from_bare_repo_signed() {
+        let repo = FixtureRepo::new("bare-source");
+        let sha1 = repo.commit(true);
+        let bare = FixtureRepo(
+            repo.0
+                .with_file_name(format!("aur-trust-test-bare-{}", std::process::id())),
+        );
+        let _ = std::fs::remove_dir_all(&bare.0);
+        repo.run(
+            "git",
+            &["clone", "--quiet", "--bare", ".", bare.0.to_str().unwrap()],
+        );
+        // A clone does not have the configuration of its origin
+        let allowed_signers = repo.0.join("allowed_signers");
+        bare.run(
+            "git",
+            &[
+                "config",
+                "gpg.ssh.allowedSignersFile",
+                allowed_signers.to_str().unwrap(),
+            ],
+        );
+        assert!(!bare.0.join(".git").exists());
+
+        let commit = head_commit_from_repo(&bare.0).unwrap();
+        assert!(sha1.starts_with(commit.sha1()));
+        let signature = commit.signature().unwrap();
+        assert_eq!(signature.validity, SignatureValidity::Good);
+        assert_eq!(signature.signer, "jane@example.com");
+        assert_eq!(commit, head_commit_from_repo(&repo.0).unwrap());
+    }
+
+    #[test]
+    fn head_commit_from_repo_unsigned() {
+        let repo = FixtureRepo::new("unsigned");
+        repo.commit(true);
+        let sha1 = repo.commit(false);
+        let commit = head_commit_from_repo(&repo.0).unwrap();
+        assert!(sha1.starts_with(commit.sha1()));
+        assert_eq!(commit.signature(), None);
+    }
+
+    #[test]
+    fn head_commit_from_repo_unknown_signature_output() {
+        let repo = FixtureRepo::new("unknown-signature");
+        repo.commit(true);
+        // Verify signatures with a program which prints garbage
+        let program = repo.0.join("garbage-verify");
+        std::fs::write(
+            &program,
+            "#!/bin/sh\necho 'Something unexpected happened' >&2\nexit 1\n",
+        )
+        .unwrap();
+        repo.run("chmod", &["+x", program.to_str().unwrap()]);
+        repo.run(
+            "git",
+            &["config", "gpg.ssh.program", program.to_str().unwrap()],
+        );
+        let error = head_commit_from_repo(&repo.0).unwrap_err();
+        assert!(
+            matches!(&error, GitError::UnknownSignature(output) if output.contains("Something unexpected")),
+            "{:?}",
+            error
+        );
+    }
+
+    #[test]
+    fn head_commit_from_repo_without_commits() {
+        let repo = FixtureRepo::new("empty");
+        let error = head_commit_from_repo(&repo.0).unwrap_err();
+        assert!(matches!(error, GitError::Failed { .. }), "{:?}", error);
+    }
+
+    /// Whether `output` claims a good signature, either from GPG or from SSH.
+    fn claims_good_signature(output: &str) -> bool {
+        output.contains("[GNUPG:] GOODSIG") || output.contains(r#"Good "git" signature"#)
+    }
+
+    #[quickcheck]
+    fn parse_verify_commit_arbitrary_input(output: String) -> bool {
+        let signature = parse_verify_commit(&output);
+        claims_good_signature(&output)
+            || signature.is_none_or(|s| s.validity != SignatureValidity::Good)
+    }
+
+    #[quickcheck]
+    fn parse_verify_commit_arbitrary_status_lines(lines: Vec<(u8, Vec<String>)>) -> bool {
+        // Build GPG status lines from known keywords with arbitrary arguments, to exercise the
+        // parser beyond the status prefix
+        let keywords = [
+            "NEWSIG",
+            "GOODSIG",
+            "BADSIG",
+            "EXPSIG",
+            "EXPKEYSIG",
+            "REVKEYSIG",
+            "ERRSIG",
+            "VALIDSIG",
+            "KEYEXPIRED",
+            "TRUST_UNDEFINED",
+            "TRUST_FULLY",
+        ];
+        let output = lines
+            .into_iter()
+            .map(|(keyword, args)| {
+                let keyword = keywords[usize::from(keyword) % keywords.len()];
+                format!("{}{} {}", STATUS_PREFIX, keyword, args.join(" "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let signature = parse_verify_commit(&output);
+        claims_good_signature(&output)
+            || signature.is_none_or(|s| s.validity != SignatureValidity::Good)
+    }
+
+    static FINGERPRINT: &str = "8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C";
+
+    fn signature(validity: SignatureValidity) -> Option<CommitSignature> {
+        Some(CommitSignature {
+            validity,
+            signer: "Jane Doe <jane@example.com>".into(),
+            key_id: "6F6D3A6D2C9A1B2C".into(),
+            fingerprint: None,
+            algorithm: None,
+            key_expiry: None,
+            sig_timestamp: None,
+        })
+    }
+
+    fn valid_signature(validity: SignatureValidity) -> Option<CommitSignature> {
+        signature(validity).map(|signature| CommitSignature {
+            fingerprint: Some(FINGERPRINT.into()),
+            algorithm: Some("EdDSA".into()),
+            sig_timestamp: Some(1669800000),
+            ..signature
+        })
+    }
+
+    #[test]
+    fn good_signature() {
+        let status = "[GNUPG:] NEWSIG
+[GNUPG:] KEY_CONSIDERED 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C 0
+[GNUPG:] SIG_ID 2mX0w5Ezbq5rM0YVjV1Bq1Wm2xQ 2022-11-30 1669800000
+[GNUPG:] GOODSIG 6F6D3A6D2C9A1B2C Jane Doe <jane@example.com>
+[GNUPG:] VALIDSIG 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C 2022-11-30 1669800000 0 4 0 22 10 00 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C
+[GNUPG:] TRUST_FULLY 0 pgp
+";
+        assert_eq!(
+            parse_verify_commit(status),
+            valid_signature(SignatureValidity::Good)
+        );
+    }
+
+    #[test]
+    fn good_signature_unknown_validity() {
+        let status = "[GNUPG:] NEWSIG
+[GNUPG:] KEY_CONSIDERED 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C 0
+[GNUPG:] GOODSIG 6F6D3A6D2C9A1B2C Jane Doe <jane@example.com>
+[GNUPG:] VALIDSIG 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C 2022-11-30 1669800000 0 4 0 22 10 00 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C
+[GNUPG:] TRUST_UNDEFINED 0 pgp
+";
+        assert_eq!(
+            parse_verify_commit(status),
+            valid_signature(SignatureValidity::UnknownValidity)
+        );
+    }
+
+    #[test]
+    fn bad_signature() {
+        let status = "[GNUPG:] NEWSIG
+[GNUPG:] KEY_CONSIDERED 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C 0
+[GNUPG:] BADSIG 6F6D3A6D2C9A1B2C Jane Doe <jane@example.com>
+";
+        assert_eq!(
+            parse_verify_commit(status),
+            signature(SignatureValidity::BadSignature)
+        );
+    }
+
+    #[test]
+    fn expired_signature() {
+        let status = "[GNUPG:] NEWSIG
+[GNUPG:] EXPSIG 6F6D3A6D2C9A1B2C Jane Doe <jane@example.com>
+[GNUPG:] VALIDSIG 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C 2022-11-30 1669800000 1669900000 4 0 22 10 00 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C
+";
+        assert_eq!(
+            parse_verify_commit(status),
+            valid_signature(SignatureValidity::ExpiredSignature)
+        );
+    }
+
+    #[test]
+    fn expired_key() {
+        let status = "[GNUPG:] NEWSIG
+[GNUPG:] KEYEXPIRED 1669900000
+[GNUPG:] EXPKEYSIG 6F6D3A6D2C9A1B2C Jane Doe <jane@example.com>
+[GNUPG:] VALIDSIG 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C 2022-11-30 1669800000 0 4 0 22 10 00 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C
+";
+        assert_eq!(
+            parse_verify_commit(status),
+            valid_signature(SignatureValidity::ExpiredKey).map(|signature| CommitSignature {
+                key_expiry: Some(1669900000),
+                ..signature
+            })
+        );
+    }
+
+    #[test]
+    fn revoked_key() {
+        let status = "[GNUPG:] NEWSIG
+[GNUPG:] KEYREVOKED
+[GNUPG:] REVKEYSIG 6F6D3A6D2C9A1B2C Jane Doe <jane@example.com>
+[GNUPG:] VALIDSIG 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C 2022-11-30 1669800000 0 4 0 22 10 00 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C
+";
+        assert_eq!(
+            parse_verify_commit(status),
+            valid_signature(SignatureValidity::RevokedKey)
+        );
+    }
+
+    #[test]
+    fn missing_key() {
+        let status = "[GNUPG:] NEWSIG
+[GNUPG:] ERRSIG 6F6D3A6D2C9A1B2C 22 10 00 1669800000 9 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C
+[GNUPG:] NO_PUBKEY 6F6D3A6D2C9A1B2C
+";
+        assert_eq!(
+            parse_verify_commit(status),
+            Some(CommitSignature {
+                validity: SignatureValidity::UnknownValidity,
+                signer: "unknown signer".into(),
+                key_id: "6F6D3A6D2C9A1B2C".into(),
+                fingerprint: Some(FINGERPRINT.into()),
+                algorithm: Some("EdDSA".into()),
+                key_expiry: None,
+                sig_timestamp: Some(1669800000),
+            })
+        );
+    }
+
+    #[test]
+    fn validsig_with_subkey() {
+        let status = "[GNUPG:] NEWSIG
+[GNUPG:] GOODSIG 1B2C3D4E5F6A7B8C Jane Doe <jane@example.com>
+[GNUPG:] VALIDSIG 0A1B2C3D4E5F60718293A4B51B2C3D4E5F6A7B8C 2022-11-30 1669800000 0 4 0 1 10 00 8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C
+[GNUPG:] TRUST_ULTIMATE 0 pgp
+";
+        let signature = parse_verify_commit(status).unwrap();
+        assert_eq!(signature.key_id, "1B2C3D4E5F6A7B8C");
+        assert_eq!(signature.fingerprint.as_deref(), Some(FINGERPRINT));
+        assert_eq!(signature.algorithm.as_deref(), Some("RSA"));
+        assert_eq!(signature.key(), FINGERPRINT);
+    }
+
+    #[test]
+    fn ssh_good_signature() {
+        let output = "Good \"git\" signature for jane@example.com with ED25519 key SHA256:fxEGPLZ8bIQDDgPsWhDF/LCKSpxVjcxrvUSFLc4oDKY\n";
+        assert_eq!(
+            parse_verify_commit(output),
+            Some(CommitSignature {
+                validity: SignatureValidity::Good,
+                signer: "jane@example.com".into(),
+                key_id: "SHA256:fxEGPLZ8bIQDDgPsWhDF/LCKSpxVjcxrvUSFLc4oDKY".into(),
+                fingerprint: Some("SHA256:fxEGPLZ8bIQDDgPsWhDF/LCKSpxVjcxrvUSFLc4oDKY".into()),
+                algorithm: Some("ssh".into()),
+                key_expiry: None,
+                sig_timestamp: None,
+            })
+        );
+    }
+
+    #[test]
+    fn ssh_good_signature_without_principal() {
+        let output = "Good \"git\" signature with ED25519 key SHA256:fxEGPLZ8bIQDDgPsWhDF/LCKSpxVjcxrvUSFLc4oDKY\n";
+        let signature = parse_verify_commit(output).unwrap();
+        assert_eq!(signature.validity, SignatureValidity::UnknownValidity);
+        assert_eq!(signature.signer, "unknown signer");
+        assert_eq!(
+            signature.key(),
+            "SHA256:fxEGPLZ8bIQDDgPsWhDF/LCKSpxVjcxrvUSFLc4oDKY"
+        );
+    }
+
+    #[test]
+    fn ssh_revoked_key() {
+        let output = "sshkey_check_revoked: Key is revoked
+Could not verify signature.
+";
+        let signature = parse_verify_commit(output).unwrap();
+        assert_eq!(signature.validity, SignatureValidity::RevokedKey);
+        assert_eq!(signature.algorithm.as_deref(), Some("ssh"));
+    }
+
+    #[test]
+    fn ssh_bad_signature() {
+        let output = "Could not verify signature.\n";
+        let signature = parse_verify_commit(output).unwrap();
+        assert_eq!(signature.validity, SignatureValidity::BadSignature);
+    }
+
+    #[test]
+    fn ssh_trusted_key() {
+        let output = "Good \"git\" signature for jane@example.com with ED25519 key SHA256:fxEGPLZ8bIQDDgPsWhDF/LCKSpxVjcxrvUSFLc4oDKY\n";
+        let commit = GitCommit::new("ae5a1b0".try_into().unwrap(), parse_verify_commit(output));
+        let trustdb = TrustDatabase::new()
+            .trust_key("SHA256:fxEGPLZ8bIQDDgPsWhDF/LCKSpxVjcxrvUSFLc4oDKY".into());
+        assert_eq!(
+            check_commit_signature(&trustdb, &commit).trust(),
+            Trust::Trusted
+        );
+        assert_eq!(
+            check_commit_signature(&TrustDatabase::new(), &commit).trust(),
+            Trust::Indeterminate
+        );
+    }
+
+    #[test]
+    fn unsigned() {
+        assert_eq!(parse_verify_commit(""), None);
+    }
+}
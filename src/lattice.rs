@@ -27,3 +27,40 @@ pub trait HasBottom {
     /// The element which is less or equal to all other elements.
     fn bottom() -> Self;
 }
+
+/// Compute both the greatest lower bound and the least upper bound of all `elements`.
+///
+/// Return `(meet, join)` of all elements, in a single pass over `elements`, or `None` if
+/// `elements` is empty.
+pub fn bounds<T, I>(elements: I) -> Option<(T, T)>
+where
+    T: MeetSemiLattice + JoinSemiLattice + Clone,
+    I: IntoIterator<Item = T>,
+{
+    let mut elements = elements.into_iter();
+    let first = elements.next()?;
+    Some(
+        elements.fold((first.clone(), first), |(lower, upper), element| {
+            (lower.meet(element.clone()), upper.join(element))
+        }),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::trust::Trust;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn bounds_empty() {
+        assert_eq!(bounds(Vec::<Trust>::new()), None);
+    }
+
+    #[quickcheck]
+    fn bounds_folds_meet_and_join(elements: Vec<Trust>) {
+        let meet = elements.iter().copied().reduce(Trust::meet);
+        let join = elements.iter().copied().reduce(Trust::join);
+        assert_eq!(bounds(elements), meet.zip(join));
+    }
+}
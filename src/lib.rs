@@ -5,7 +5,30 @@
 #![deny(warnings, missing_docs, clippy::all)]
 
 //! Check trust in AUR packages.
+//!
+//! The trust checks themselves work offline, with evidence gathered elsewhere, and do not need
+//! the [AUR RPC client](aur::rpc::AurRpcClient):
+//!
+//! ```
+//! use std::collections::HashSet;
+//!
+//! use aur_trust::{check_trust, GitCommit, PackageWithEvidence, Trust, TrustDatabase};
+//!
+//! let trustdb = TrustDatabase::new().trust_maintainer("alice".into());
+//! let package = PackageWithEvidence::new(
+//!     "foo".into(),
+//!     HashSet::from(["alice".into()]),
+//!     GitCommit::new("ae5a1b0".try_into().unwrap(), None),
+//! );
+//! assert_eq!(check_trust(&trustdb, &package).trust(), Trust::Trusted);
+//! ```
 
 pub mod aur;
+pub mod git;
 pub mod lattice;
 pub mod trust;
+
+pub use trust::{
+    check_commit_signature, check_maintainers, check_trust, combined_verdict, GitCommit,
+    PackageWithEvidence, Trust, TrustDatabase, TrustVerdict,
+};
@@ -4,6 +4,1916 @@
 
 //! Determine whether AUR packages are trusted.
 
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+use futures::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize, Serializer};
+use thiserror::Error;
+use tokio::time::{timeout_at, Duration, Instant};
+use tracing::{event, instrument, Level};
+
+use crate::aur::rpc::{self, AurInfoProvider};
+use crate::git::{head_commit_from_repo, GitError};
+use crate::lattice::{HasBottom, HasTop, JoinSemiLattice, MeetSemiLattice};
+use signature::check_signature_validity;
+
+mod age;
+mod attestation;
+mod config;
+mod maintainer;
+mod popularity;
+mod signature;
+mod tag;
 mod types;
 
-pub use types::Trust;
+pub use age::check_package_age;
+pub use attestation::to_attestation;
+pub use config::{ConfigError, ConfigSource};
+pub use maintainer::{check_maintainers, check_orphaned, classify_maintainers};
+pub use popularity::check_popularity;
+pub use signature::{
+    check_commit_chain, check_commit_signature, check_commit_signature_against,
+    check_commit_signature_strict, check_signer_is_maintainer,
+};
+pub use tag::{check_commit_or_tag_signature, check_tag_signature};
+pub use types::{
+    CommitSignature, GitCommit, GitTag, MaintainerStatus, PackageWithEvidence, Severity,
+    Sha1Abbrev, Sha1AbbrevError, SignatureValidity, TagSignature, Trust, TrustReason, TrustReport,
+    TrustSummary, TrustVerdict, ValidationWarning,
+};
+
+/// How to combine the verdicts on the signature and on the maintainers of a package.
+///
+/// See [`check_trust_with_policy`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum TrustPolicy {
+    /// Trust a package if either its signature or its maintainers are trusted.
+    #[default]
+    Either,
+    /// Trust a package only if both its signature and its maintainers are trusted.
+    Both,
+}
+
+/// A database of trusted entities.
+///
+/// Serialize a trust database to audit the trust configuration; all sets serialize as sorted
+/// arrays, for stable output.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrustDatabase {
+    #[serde(serialize_with = "serialize_sorted")]
+    trusted_maintainers: HashSet<String>,
+    #[serde(serialize_with = "serialize_sorted")]
+    trusted_keys: HashSet<String>,
+    #[serde(serialize_with = "serialize_sorted")]
+    trusted_signers: HashSet<String>,
+    #[serde(serialize_with = "serialize_sorted")]
+    distrusted_maintainers: HashSet<String>,
+    maintainer_signers: BTreeMap<String, BTreeSet<String>>,
+    require_signature: bool,
+    ignore_co_maintainers: bool,
+    strict_maintainers: bool,
+    min_votes: Option<u32>,
+}
+
+/// Serialize `set` as sorted sequence.
+fn serialize_sorted<S: Serializer>(
+    set: &HashSet<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(set.iter().collect::<BTreeSet<_>>())
+}
+
+/// Whether `key` looks like an SSH key fingerprint, or a GPG key ID or fingerprint.
+fn is_well_formed_key(key: &str) -> bool {
+    match key.strip_prefix("SHA256:") {
+        Some(fingerprint) => {
+            fingerprint.len() == 43
+                && fingerprint
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/')
+        }
+        None => (key.len() == 16 || key.len() == 40) && key.chars().all(|c| c.is_ascii_hexdigit()),
+    }
+}
+
+impl TrustDatabase {
+    /// Create an empty trust database which trusts nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust the given `maintainer`.
+    pub fn trust_maintainer(mut self, maintainer: String) -> Self {
+        self.trust_maintainer_mut(maintainer);
+        self
+    }
+
+    /// Trust the given `maintainer` in place.
+    ///
+    /// See [`TrustDatabase::trust_maintainer`].
+    pub fn trust_maintainer_mut(&mut self, maintainer: String) {
+        self.trusted_maintainers.insert(maintainer);
+    }
+
+    /// Distrust the given `maintainer`.
+    ///
+    /// A distrusted maintainer makes all their packages untrusted, even if the maintainer is
+    /// trusted as well.
+    pub fn distrust_maintainer(mut self, maintainer: String) -> Self {
+        self.distrusted_maintainers.insert(maintainer);
+        self
+    }
+
+    /// Trust the given signing `key`.
+    ///
+    /// `key` is either the ID or the full fingerprint of the signing key.  A good signature only
+    /// makes a commit trusted if made with a trusted key.
+    pub fn trust_key(mut self, key: String) -> Self {
+        self.trusted_keys.insert(key);
+        self
+    }
+
+    /// Trust signers matching the given `pattern`.
+    ///
+    /// `pattern` is either a complete user ID such as `Jane Doe <jane@example.com>`, a bare email
+    /// address such as `jane@example.com`, or an email domain such as `@example.com`.  Email
+    /// addresses and domains match case-insensitively.
+    ///
+    /// A good signature by a matching signer makes a commit trusted, like a good signature by a
+    /// trusted key.
+    pub fn trust_signer(mut self, pattern: String) -> Self {
+        self.trusted_signers.insert(pattern);
+        self
+    }
+
+    /// Identify `maintainer` by signers matching `pattern`.
+    ///
+    /// `pattern` has the same format as for [`TrustDatabase::trust_signer`].  Associating
+    /// signers with maintainers does not trust any signer or maintainer; it only allows to check
+    /// that a signer is a maintainer of a package with [`check_signer_is_maintainer`].
+    pub fn maintainer_signer(mut self, maintainer: String, pattern: String) -> Self {
+        self.maintainer_signers
+            .entry(maintainer)
+            .or_default()
+            .insert(pattern);
+        self
+    }
+
+    /// Whether to require a good signature on the HEAD commit of every package.
+    ///
+    /// If set, a package whose HEAD commit has no good signature is untrusted, even if all its
+    /// maintainers are trusted.  Off by default.
+    pub fn require_signature(mut self, require: bool) -> Self {
+        self.require_signature = require;
+        self
+    }
+
+    /// Whether to only consider the primary maintainer of packages.
+    ///
+    /// If set, co-maintainers of a package do not affect trust in the package, and only appear
+    /// as informational reasons.  Off by default.
+    pub fn ignore_co_maintainers(mut self, ignore: bool) -> Self {
+        self.ignore_co_maintainers = ignore;
+        self
+    }
+
+    /// Whether to distrust packages with maintainers which are not trusted.
+    ///
+    /// If set, a package with any maintainer who is not trusted is untrusted, even if its HEAD
+    /// commit has a good signature by a trusted key.  Packages whose maintainers are unknown
+    /// remain indeterminate.  Off by default, where a maintainer who is not trusted makes trust
+    /// in a package indeterminate only.
+    pub fn strict_maintainers(mut self, strict: bool) -> Self {
+        self.strict_maintainers = strict;
+        self
+    }
+
+    /// Require at least `min_votes` votes for packages.
+    ///
+    /// If set, packages with fewer votes are at most indeterminate, even if trusted otherwise.
+    /// Votes never make a package trusted on their own.  Not set by default.
+    pub fn min_votes(mut self, min_votes: Option<u32>) -> Self {
+        self.min_votes = min_votes;
+        self
+    }
+
+    /// Merge this trust database with `other`.
+    ///
+    /// Trust all maintainers and keys trusted in either database, and distrust all maintainers
+    /// distrusted in either database.  Distrust takes precedence: a maintainer distrusted in one
+    /// database is not trusted in the merged database, even if trusted in the other.  Identify
+    /// maintainers by the signers associated with them in either database.
+    ///
+    /// For all other settings the stricter one wins: require signatures and strict maintainers if
+    /// either database does, only ignore co-maintainers if both databases do, and require the
+    /// higher minimum number of votes.
+    pub fn merge(mut self, other: Self) -> Self {
+        self.trusted_maintainers.extend(other.trusted_maintainers);
+        self.trusted_keys.extend(other.trusted_keys);
+        self.trusted_signers.extend(other.trusted_signers);
+        self.distrusted_maintainers
+            .extend(other.distrusted_maintainers);
+        for (maintainer, patterns) in other.maintainer_signers {
+            self.maintainer_signers
+                .entry(maintainer)
+                .or_default()
+                .extend(patterns);
+        }
+        let distrusted = &self.distrusted_maintainers;
+        self.trusted_maintainers.retain(|m| !distrusted.contains(m));
+        self.require_signature |= other.require_signature;
+        self.ignore_co_maintainers &= other.ignore_co_maintainers;
+        self.strict_maintainers |= other.strict_maintainers;
+        self.min_votes = self.min_votes.max(other.min_votes);
+        self
+    }
+
+    /// Check this trust database for likely mistakes.
+    ///
+    /// Warn about every trusted key which is neither a SHA256 fingerprint of an SSH key, i.e.
+    /// `SHA256:` followed by 43 base64 characters, nor a long GPG key ID or a full GPG key
+    /// fingerprint, i.e. 16 or 40 hexadecimal digits.  Such keys most likely never match the key
+    /// of any signature, e.g. because the `SHA256:` prefix is missing.
+    ///
+    /// Return all warnings in a stable order.
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        let mut warnings = self
+            .trusted_keys
+            .iter()
+            .filter(|key| !is_well_formed_key(key))
+            .map(|key| ValidationWarning::MalformedKey(key.clone()))
+            .collect::<Vec<_>>();
+        warnings.sort();
+        warnings
+    }
+
+    /// Whether the given `maintainer` is trusted.
+    pub fn is_trusted_maintainer(&self, maintainer: &str) -> bool {
+        self.trusted_maintainers.contains(maintainer)
+    }
+
+    /// Whether the given `maintainer` is distrusted.
+    pub fn is_distrusted_maintainer(&self, maintainer: &str) -> bool {
+        self.distrusted_maintainers.contains(maintainer)
+    }
+
+    /// Whether the given signing `key` is trusted.
+    pub fn is_trusted_key(&self, key: &str) -> bool {
+        self.trusted_keys.contains(key)
+    }
+
+    /// Whether the given `signer` is trusted.
+    ///
+    /// `signer` is the user ID of a signing key; see [`TrustDatabase::trust_signer`].
+    pub fn is_trusted_signer(&self, signer: &str) -> bool {
+        self.trusted_signers.contains(signer)
+            || self
+                .trusted_signers
+                .iter()
+                .any(|pattern| signature::signer_matches(pattern, signer))
+    }
+
+    /// Whether the given `signer` is the given `maintainer`.
+    ///
+    /// See [`TrustDatabase::maintainer_signer`].
+    pub fn is_maintainer_signer(&self, maintainer: &str, signer: &str) -> bool {
+        self.maintainer_signers
+            .get(maintainer)
+            .is_some_and(|patterns| {
+                patterns
+                    .iter()
+                    .any(|pattern| signature::signer_matches(pattern, signer))
+            })
+    }
+}
+
+/// Check whether the given `package` is trusted.
+///
+/// Takes the upper bound of the verdicts on the signature of the HEAD commit and on the
+/// maintainers of the package, i.e. either a good signature or trusted maintainers suffice to
+/// trust the package.
+///
+/// If `trustdb` requires signatures, a HEAD commit without a good signature makes the package
+/// untrusted regardless of its maintainers.
+pub fn check_trust(trustdb: &TrustDatabase, package: &PackageWithEvidence) -> TrustVerdict {
+    check_trust_report(trustdb, package).verdict().clone()
+}
+
+/// Check whether the given `package` is trusted, and report the verdicts of all checks.
+///
+/// The combined verdict of the report is the verdict of [`check_trust`]; the report also has the
+/// verdicts on the signature of the HEAD commit and on the maintainers of the package.
+pub fn check_trust_report(trustdb: &TrustDatabase, package: &PackageWithEvidence) -> TrustReport {
+    check_trust_report_with_policy(trustdb, package, TrustPolicy::Either)
+}
+
+/// Check whether the given `package` is trusted, combining checks according to `policy`.
+///
+/// With [`TrustPolicy::Either`] this is the same as [`check_trust`].  With [`TrustPolicy::Both`]
+/// take the lower bound of the verdicts on the signature of the HEAD commit and on the
+/// maintainers of the package, i.e. a package is only trusted if its HEAD commit has a good
+/// signature by a trusted key and all its maintainers are trusted.  Indeterminate trust in
+/// either is not sufficient to trust the package:
+///
+/// | Signature \ Maintainers | Trusted       | Indeterminate | Untrusted |
+/// |------------------------|---------------|---------------|-----------|
+/// | Trusted                | Trusted       | Indeterminate | Untrusted |
+/// | Indeterminate          | Indeterminate | Indeterminate | Untrusted |
+/// | Untrusted              | Untrusted     | Untrusted     | Untrusted |
+pub fn check_trust_with_policy(
+    trustdb: &TrustDatabase,
+    package: &PackageWithEvidence,
+    policy: TrustPolicy,
+) -> TrustVerdict {
+    check_trust_report_with_policy(trustdb, package, policy)
+        .verdict()
+        .clone()
+}
+
+#[instrument(name = "check_trust", skip(trustdb, package), fields(package = %package.name))]
+fn check_trust_report_with_policy(
+    trustdb: &TrustDatabase,
+    package: &PackageWithEvidence,
+    policy: TrustPolicy,
+) -> TrustReport {
+    let commit_verdict = check_commit_signature(trustdb, &package.head_commit);
+    event!(
+        Level::DEBUG,
+        "Signature of commit {}: {}",
+        package.head_commit.abbrev_sha1,
+        commit_verdict
+    );
+    let maintainer_verdict = check_package_maintainers(trustdb, package);
+    event!(Level::DEBUG, "Maintainers: {}", maintainer_verdict);
+    let verdict = lower_unpopular(
+        trustdb,
+        package,
+        combine_signature_and_maintainers(
+            trustdb,
+            policy,
+            commit_verdict.clone(),
+            maintainer_verdict.clone(),
+        ),
+    );
+    TrustReport::new(
+        package.name.clone(),
+        verdict,
+        commit_verdict,
+        maintainer_verdict,
+    )
+}
+
+/// Check whether each of the given `packages` is trusted.
+///
+/// Return the verdict for each package along with its name, ordered by package name.
+///
+/// Checking packages with evidence is pure computation; to gather evidence for many packages
+/// concurrently, see [`check_cloned_packages`].
+pub fn check_all_trust(
+    trustdb: &TrustDatabase,
+    packages: &[PackageWithEvidence],
+) -> Vec<(String, TrustVerdict)> {
+    let mut results = packages
+        .iter()
+        .map(|package| (package.name.clone(), check_trust(trustdb, package)))
+        .collect::<Vec<_>>();
+    results.sort_by(|(a, _), (b, _)| a.cmp(b));
+    results
+}
+
+/// Gathering evidence to check trust in a package failed.
+#[derive(Error, Debug)]
+pub enum CheckError {
+    /// Looking up the package in the AUR failed.
+    #[error("failed to look up package in the AUR")]
+    Aur(#[from] rpc::AurRpcError),
+    /// Reading the git repository of the package failed.
+    #[error("failed to read git repository of package")]
+    Git(#[source] GitError),
+    /// Parsing the signature of the HEAD commit of the package failed.
+    #[error("failed to parse signature of package")]
+    Signature(#[source] GitError),
+}
+
+impl From<GitError> for CheckError {
+    fn from(error: GitError) -> Self {
+        match error {
+            GitError::UnknownSignature(_) => CheckError::Signature(error),
+            _ => CheckError::Git(error),
+        }
+    }
+}
+
+/// How many packages [`check_cloned_packages`] looks up concurrently.
+const CHECK_CONCURRENCY: usize = 8;
+
+/// Check whether each of the given cloned packages is trusted.
+///
+/// `clones` holds names of packages along with the paths of their git clones.  For every package
+/// read the HEAD commit from its clone with [`head_commit_from_repo`], and check the package with
+/// [`check_trust_by_name`].  Look up up to eight packages in the AUR concurrently.
+///
+/// Return the verdict or the error for each package along with its name, ordered by package
+/// name.  Unlike [`check_all_trust`] every package can fail individually; see [`CheckError`]
+/// to decide whether to treat a failure as indeterminate trust or to abort.
+pub async fn check_cloned_packages<P: AsRef<Path>>(
+    client: &impl AurInfoProvider,
+    trustdb: &TrustDatabase,
+    clones: &[(String, P)],
+) -> Vec<(String, Result<TrustVerdict, CheckError>)> {
+    check_cloned_packages_until(client, trustdb, clones, None).await
+}
+
+/// Check whether each of the given cloned packages is trusted, within `budget`.
+///
+/// Like [`check_cloned_packages`], but stop checking packages once `budget` elapsed, and return
+/// the results of all packages checked so far, along with an indeterminate verdict with
+/// [`TrustReason::TimedOut`] for every package whose check did not finish in time.
+///
+/// Note that reading the HEAD commit of a clone blocks, so the budget can only interrupt AUR
+/// lookups.
+pub async fn check_cloned_packages_with_budget<P: AsRef<Path>>(
+    client: &impl AurInfoProvider,
+    trustdb: &TrustDatabase,
+    clones: &[(String, P)],
+    budget: Duration,
+) -> Vec<(String, Result<TrustVerdict, CheckError>)> {
+    let deadline = Instant::now() + budget;
+    check_cloned_packages_until(client, trustdb, clones, Some(deadline)).await
+}
+
+/// Check whether each of the given cloned packages is trusted, until the optional `deadline`.
+async fn check_cloned_packages_until<P: AsRef<Path>>(
+    client: &impl AurInfoProvider,
+    trustdb: &TrustDatabase,
+    clones: &[(String, P)],
+    deadline: Option<Instant>,
+) -> Vec<(String, Result<TrustVerdict, CheckError>)> {
+    let mut checks = stream::iter(clones.iter().enumerate())
+        .map(|(index, (name, path))| async move {
+            let result = match head_commit_from_repo(path.as_ref()) {
+                Ok(head_commit) => check_trust_by_name(client, trustdb, name, head_commit)
+                    .await
+                    .map_err(CheckError::from),
+                Err(error) => Err(CheckError::from(error)),
+            };
+            (index, result)
+        })
+        .buffer_unordered(CHECK_CONCURRENCY);
+    let mut finished = clones.iter().map(|_| None).collect::<Vec<_>>();
+    let check_all = async {
+        while let Some((index, result)) = checks.next().await {
+            finished[index] = Some(result);
+        }
+    };
+    match deadline {
+        Some(deadline) => {
+            if timeout_at(deadline, check_all).await.is_err() {
+                event!(Level::WARN, "Checking cloned packages timed out");
+            }
+        }
+        None => check_all.await,
+    }
+    let mut results = clones
+        .iter()
+        .zip(finished)
+        .map(|((name, _), result)| (name.clone(), result.unwrap_or_else(|| Ok(timed_out()))))
+        .collect::<Vec<_>>();
+    results.sort_by(|(a, _), (b, _)| a.cmp(b));
+    results
+}
+
+/// An indeterminate verdict for a check which did not finish in time.
+fn timed_out() -> TrustVerdict {
+    TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::TimedOut)
+}
+
+/// Check the maintainers of `root` and of all its AUR dependencies.
+///
+/// Walk the runtime and build dependencies of `root` recursively, and check the maintainers of
+/// every package found in the AUR with [`check_maintainers`].  Skip dependencies which are not
+/// in the AUR, e.g. packages from the official repositories.  Visit every package only once, so
+/// cycles in the dependency graph do not matter.
+///
+/// Return verdicts for all AUR packages by package name, including `root`; if `root` is not in
+/// the AUR return an empty map.
+///
+/// Look up packages with `client`, which is usually an [`rpc::AurRpcClient`].
+pub async fn check_trust_recursive(
+    client: &impl AurInfoProvider,
+    trustdb: &TrustDatabase,
+    root: &str,
+) -> rpc::Result<HashMap<String, TrustVerdict>> {
+    let mut verdicts = HashMap::new();
+    let mut seen = HashSet::from([root.to_string()]);
+    let mut pending = vec![root.to_string()];
+    while !pending.is_empty() {
+        let mut dependencies = Vec::new();
+        for package in client.info(&pending).await? {
+            let (name, verdict) =
+                check_dependency_maintainers(trustdb, package, &mut seen, &mut dependencies);
+            verdicts.insert(name, verdict);
+        }
+        pending = dependencies;
+    }
+    Ok(verdicts)
+}
+
+/// Check the whole AUR dependency closure of `root`.
+///
+/// Check `root` and all its AUR dependencies with [`check_trust_recursive`], and roll up the
+/// verdicts of the closure with [`combined_verdict`], i.e. the closure is only as trusted as its
+/// least trusted package.
+///
+/// Return the verdicts for all AUR packages in the closure by package name, along with the
+/// rolled-up verdict.  If `root` is not in the AUR, return an empty map and an indeterminate
+/// verdict, because there's nothing to trust.
+pub async fn check_dependency_closure(
+    client: &impl AurInfoProvider,
+    trustdb: &TrustDatabase,
+    root: &str,
+) -> rpc::Result<(HashMap<String, TrustVerdict>, TrustVerdict)> {
+    let verdicts = check_trust_recursive(client, trustdb, root).await?;
+    let verdict = combined_verdict(verdicts.values().cloned());
+    Ok((verdicts, verdict))
+}
+
+/// Check the maintainers of `root` and of all its AUR dependencies as a stream.
+///
+/// Like [`check_trust_recursive`], but yield the verdict for every package as soon as it is
+/// available, instead of collecting all verdicts first.  The stream yields every AUR package
+/// only once, so cycles in the dependency graph do not matter.
+///
+/// If looking up a batch of packages fails, yield the error for the first package of the batch
+/// and end the stream, because the dependencies of the batch remain unknown.
+pub fn check_trust_recursive_stream<'a, C: AurInfoProvider>(
+    client: &'a C,
+    trustdb: &'a TrustDatabase,
+    root: &str,
+) -> impl Stream<Item = (String, rpc::Result<TrustVerdict>)> + 'a {
+    recursive_stream(client, trustdb, root, None)
+}
+
+/// Check the maintainers of `root` and of all its AUR dependencies within `budget`.
+///
+/// Like [`check_trust_recursive_stream`], but stop looking up packages once `budget` elapsed.
+/// Yield verdicts for all packages checked so far, and an indeterminate verdict with
+/// [`TrustReason::TimedOut`] for every package whose lookup did not finish in time, then end
+/// the stream.  Dependencies of these packages remain unknown and yield no verdict.
+pub fn check_trust_recursive_stream_with_budget<'a, C: AurInfoProvider>(
+    client: &'a C,
+    trustdb: &'a TrustDatabase,
+    root: &str,
+    budget: Duration,
+) -> impl Stream<Item = (String, rpc::Result<TrustVerdict>)> + 'a {
+    recursive_stream(client, trustdb, root, Some(Instant::now() + budget))
+}
+
+/// Check `root` and all its AUR dependencies as a stream, until the optional `deadline`.
+fn recursive_stream<'a, C: AurInfoProvider>(
+    client: &'a C,
+    trustdb: &'a TrustDatabase,
+    root: &str,
+    deadline: Option<Instant>,
+) -> impl Stream<Item = (String, rpc::Result<TrustVerdict>)> + 'a {
+    let seen = HashSet::from([root.to_string()]);
+    let pending = vec![root.to_string()];
+    let checked = VecDeque::new();
+    stream::unfold(
+        (seen, pending, checked),
+        move |(mut seen, mut pending, mut checked)| async move {
+            loop {
+                if let Some((name, verdict)) = checked.pop_front() {
+                    return Some(((name, Ok(verdict)), (seen, pending, checked)));
+                }
+                if pending.is_empty() {
+                    return None;
+                }
+                let batch = std::mem::take(&mut pending);
+                let result = match deadline {
+                    Some(deadline) => match timeout_at(deadline, client.info(&batch)).await {
+                        Ok(result) => result,
+                        Err(_) => {
+                            event!(Level::WARN, "Verification of {:?} timed out", batch);
+                            checked.extend(batch.into_iter().map(|name| (name, timed_out())));
+                            continue;
+                        }
+                    },
+                    None => client.info(&batch).await,
+                };
+                match result {
+                    Ok(packages) => {
+                        for package in packages {
+                            checked.push_back(check_dependency_maintainers(
+                                trustdb,
+                                package,
+                                &mut seen,
+                                &mut pending,
+                            ));
+                        }
+                    }
+                    Err(error) => {
+                        let name = batch.into_iter().next().unwrap_or_default();
+                        return Some(((name, Err(error)), (seen, pending, checked)));
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Check the maintainers of `package` while walking dependencies.
+///
+/// Add all dependencies of `package` not `seen` yet to `dependencies`, and return the name of
+/// `package` along with the verdict about its maintainers.
+fn check_dependency_maintainers(
+    trustdb: &TrustDatabase,
+    package: rpc::AurPackage,
+    seen: &mut HashSet<String>,
+    dependencies: &mut Vec<String>,
+) -> (String, TrustVerdict) {
+    let maintainers = package
+        .maintainer
+        .iter()
+        .chain(&package.co_maintainers)
+        .cloned()
+        .collect();
+    for dependency in package.depends.iter().chain(&package.make_depends) {
+        let name = dependency_name(dependency);
+        if seen.insert(name.to_string()) {
+            dependencies.push(name.to_string());
+        }
+    }
+    (package.name, check_maintainers(trustdb, &maintainers))
+}
+
+/// Check whether the package with the given `name` is trusted.
+///
+/// Look up the maintainers of the package with `client`, and check trust in the package with
+/// these maintainers and the given `head_commit` with [`check_trust`].  If the package does not
+/// exist in the AUR its maintainers are unknown, so the maintainers give no evidence either way.
+pub async fn check_trust_by_name(
+    client: &impl AurInfoProvider,
+    trustdb: &TrustDatabase,
+    name: &str,
+    head_commit: GitCommit,
+) -> rpc::Result<TrustVerdict> {
+    let package = match client.info_one(name).await? {
+        Some(package) => PackageWithEvidence::from_aur_package(&package, head_commit),
+        None => PackageWithEvidence::new(name.to_string(), HashSet::new(), head_commit),
+    };
+    Ok(check_trust(trustdb, &package))
+}
+
+/// The name of the package in the given `dependency`, without any version constraint.
+fn dependency_name(dependency: &str) -> &str {
+    dependency
+        .split(['<', '>', '='])
+        .next()
+        .unwrap_or(dependency)
+}
+
+/// Check the maintainers of `package`, ignoring co-maintainers if `trustdb` says so.
+fn check_package_maintainers(
+    trustdb: &TrustDatabase,
+    package: &PackageWithEvidence,
+) -> TrustVerdict {
+    if trustdb.ignore_co_maintainers {
+        let primary = package.primary_maintainer.iter().cloned().collect();
+        let mut co_maintainers = package
+            .maintainers
+            .iter()
+            .filter(|m| package.primary_maintainer.as_ref() != Some(m))
+            .collect::<Vec<_>>();
+        co_maintainers.sort();
+        co_maintainers
+            .into_iter()
+            .fold(check_maintainers(trustdb, &primary), |verdict, m| {
+                verdict.add_reason(TrustReason::CoMaintainerIgnored {
+                    maintainer: m.clone(),
+                })
+            })
+    } else {
+        check_maintainers(trustdb, &package.maintainers)
+    }
+}
+
+fn combine_signature_and_maintainers(
+    trustdb: &TrustDatabase,
+    policy: TrustPolicy,
+    signature_verdict: TrustVerdict,
+    maintainer_verdict: TrustVerdict,
+) -> TrustVerdict {
+    if trustdb.require_signature && signature_verdict.trust() != Trust::Trusted {
+        signature_verdict
+            .set_trust(Trust::Untrusted)
+            .add_reason(TrustReason::SignatureRequired)
+    } else if trustdb.strict_maintainers && maintainer_verdict.trust() == Trust::Untrusted {
+        maintainer_verdict
+    } else {
+        match policy {
+            TrustPolicy::Either => signature_verdict.join(maintainer_verdict),
+            TrustPolicy::Both => signature_verdict.meet(maintainer_verdict),
+        }
+    }
+}
+
+/// Lower the `verdict` for `package` if it has fewer votes than `trustdb` requires.
+///
+/// Leave the verdict alone if the package has no known number of votes.
+fn lower_unpopular(
+    trustdb: &TrustDatabase,
+    package: &PackageWithEvidence,
+    verdict: TrustVerdict,
+) -> TrustVerdict {
+    match (trustdb.min_votes, package.num_votes) {
+        (Some(min_votes), Some(votes)) => verdict.meet(popularity::check_votes(votes, min_votes)),
+        _ => verdict,
+    }
+}
+
+/// Combine many `verdicts` into a single verdict.
+///
+/// Compute the meet of all verdicts, i.e. the combined verdict is only as trusted as the least
+/// trusted verdict, and has the reasons of all verdicts with this least trust.
+///
+/// Stop at the first untrusted verdict, because the meet with an untrusted verdict stays
+/// untrusted, and do not consume any further verdicts, which may be expensive to compute.  The
+/// combined verdict then only has the reasons of this first untrusted verdict; reasons of later
+/// untrusted verdicts are lost.  Hence the order of `verdicts` does not affect the trust of the
+/// combined verdict, but with more than one untrusted verdict it affects its reasons.  Use
+/// [`combined_verdict_explained`] to see the reasons of all verdicts.
+///
+/// Start from the top verdict, i.e. [`TrustVerdict::top`], which is the identity of meet, so a
+/// single verdict combines to itself.  An empty `verdicts` however does not give any evidence for
+/// trust, so return an indeterminate verdict without reasons if `verdicts` is empty, instead of
+/// the trusted top verdict.
+pub fn combined_verdict<I>(verdicts: I) -> TrustVerdict
+where
+    I: IntoIterator<Item = TrustVerdict>,
+{
+    let mut verdicts = verdicts.into_iter().peekable();
+    if verdicts.peek().is_none() {
+        return TrustVerdict::default();
+    }
+    let mut combined = TrustVerdict::top();
+    while combined.trust() != Trust::bottom() {
+        match verdicts.next() {
+            Some(verdict) => combined = combined.meet(verdict),
+            None => break,
+        }
+    }
+    combined
+}
+
+/// Combine many `verdicts` into a single verdict, and explain the combined verdict.
+///
+/// Compute the combined verdict like [`combined_verdict`], and return it along with the trust and
+/// the reasons of every verdict in `verdicts`, in order.  The explanation has all verdicts,
+/// including those whose reasons do not appear in the combined verdict, i.e. more trusted
+/// verdicts.
+pub fn combined_verdict_explained<I>(verdicts: I) -> (TrustVerdict, Vec<(Trust, Vec<String>)>)
+where
+    I: IntoIterator<Item = TrustVerdict>,
+{
+    let verdicts = verdicts.into_iter().collect::<Vec<_>>();
+    let explanation = verdicts
+        .iter()
+        .map(|verdict| {
+            let reasons = verdict.reasons().iter().map(ToString::to_string).collect();
+            (verdict.trust(), reasons)
+        })
+        .collect();
+    (combined_verdict(verdicts), explanation)
+}
+
+/// Group the reasons of verdicts for many packages by reason.
+///
+/// `results` holds verdicts for packages by package name.  Return every distinct reason, in the
+/// order of [`TrustReason`], along with the names of all packages whose verdict has this reason,
+/// in the order of `results`.
+pub fn group_reasons<'a, I>(results: I) -> Vec<(TrustReason, Vec<String>)>
+where
+    I: IntoIterator<Item = &'a (String, TrustVerdict)>,
+{
+    let mut groups: BTreeMap<&TrustReason, Vec<String>> = BTreeMap::new();
+    for (package, verdict) in results {
+        for reason in verdict.reasons() {
+            let packages = groups.entry(reason).or_default();
+            if !packages.contains(package) {
+                packages.push(package.clone());
+            }
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(reason, packages)| (reason.clone(), packages))
+        .collect()
+}
+
+/// Count trusted, indeterminate and untrusted `verdicts`.
+pub fn summarize<'a, I>(verdicts: I) -> TrustSummary
+where
+    I: IntoIterator<Item = &'a TrustVerdict>,
+{
+    verdicts
+        .into_iter()
+        .fold(TrustSummary::default(), |summary, verdict| {
+            summary.count(verdict.trust())
+        })
+}
+
+/// Keys seen on first use, by package base.
+pub type TofuStore = HashMap<String, String>;
+
+/// Check the signature of the HEAD commit of `package` with trust on first use.
+///
+/// If the HEAD commit has a good signature and `store` has no key for the package base yet,
+/// record the signing key and trust the commit.  If `store` already has a key for the package
+/// base, trust the commit only if it is signed with the same key, and distrust it otherwise.
+///
+/// Record keys by [package base](PackageWithEvidence::package_base), because all packages of a
+/// base share the same git repository and thus the same HEAD commit.
+///
+/// Keys are trusted on first use only; the trusted keys of the trust database do not apply.
+/// Commits without a good signature are checked like with [`check_commit_signature`], and do
+/// not record any key.
+pub fn check_commit_signature_tofu(
+    store: &mut TofuStore,
+    package: &PackageWithEvidence,
+) -> TrustVerdict {
+    let commit = &package.head_commit;
+    let base = package.package_base();
+    match &commit.signature {
+        Some(signature) if signature.validity == SignatureValidity::Good => match store.get(base) {
+            None => {
+                store.insert(base.to_string(), signature.key().to_string());
+                check_signature_validity(commit).add_reason(TrustReason::KeyTrustedOnFirstUse {
+                    package: base.to_string(),
+                    key: signature.key().to_string(),
+                })
+            }
+            Some(key) if signature.keys().any(|k| is_same_key(k, key)) => {
+                check_signature_validity(commit)
+            }
+            Some(key) => TrustVerdict::new(Trust::Untrusted).add_reason(
+                TrustReason::KeyChangedSinceFirstUse {
+                    sha1: commit.abbrev_sha1.to_string(),
+                    package: base.to_string(),
+                    key: signature.key().to_string(),
+                    first_key: key.clone(),
+                },
+            ),
+        },
+        _ => check_signature_validity(commit),
+    }
+}
+
+/// Whether the keys `a` and `b` denote the same key.
+///
+/// Either both keys are equal, or one is a GPG key fingerprint which ends with the other, i.e.
+/// the other is the key ID of the fingerprint.  This way a key recorded by fingerprint on first
+/// use still matches a later signature whose fingerprint git did not report.
+fn is_same_key(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    short == long
+        || (16 <= short.len()
+            && long.chars().all(|c| c.is_ascii_hexdigit())
+            && long
+                .to_ascii_uppercase()
+                .ends_with(&short.to_ascii_uppercase()))
+}
+
+/// Check whether the given `package` is trusted, with trust on first use for signatures.
+///
+/// Like [`check_trust`], but checks the signature of the HEAD commit with
+/// [`check_commit_signature_tofu`].
+#[instrument(skip(trustdb, store, package), fields(package = %package.name))]
+pub fn check_trust_tofu(
+    trustdb: &TrustDatabase,
+    store: &mut TofuStore,
+    package: &PackageWithEvidence,
+) -> TrustVerdict {
+    let verdict = combine_signature_and_maintainers(
+        trustdb,
+        TrustPolicy::Either,
+        check_commit_signature_tofu(store, package),
+        check_package_maintainers(trustdb, package),
+    );
+    lower_unpopular(trustdb, package, verdict)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aur::mock::{json_response, serve};
+    use crate::aur::rpc::{AurPackage, AurRpcClient};
+    use async_trait::async_trait;
+    use pretty_assertions::assert_eq;
+    use quickcheck_macros::quickcheck;
+    use tracing_test::traced_test;
+
+    fn package(key: &str) -> PackageWithEvidence {
+        PackageWithEvidence {
+            name: "foo".into(),
+            package_base: None,
+            maintainers: HashSet::new(),
+            primary_maintainer: None,
+            head_commit: GitCommit {
+                abbrev_sha1: "ae5a1b0".try_into().unwrap(),
+                signature: Some(CommitSignature {
+                    validity: SignatureValidity::Good,
+                    signer: "Jane Doe <jane@example.com>".into(),
+                    key_id: key.into(),
+                    fingerprint: None,
+                    algorithm: None,
+                    key_expiry: None,
+                    sig_timestamp: None,
+                }),
+            },
+            num_votes: None,
+        }
+    }
+
+    #[test]
+    fn check_trust_good_signature_untrusted_maintainer() {
+        let mut package = package("SHA256:key");
+        package.maintainers.insert("bar".into());
+        let trustdb = TrustDatabase::new().trust_key("SHA256:key".into());
+        let verdict = check_trust(&trustdb, &package);
+        assert_eq!(verdict.trust(), Trust::Trusted);
+    }
+
+    #[test]
+    fn trust_maintainer_mut() {
+        let mut trustdb = TrustDatabase::new();
+        for maintainer in ["foo", "bar"] {
+            trustdb.trust_maintainer_mut(maintainer.into());
+        }
+        assert_eq!(
+            trustdb,
+            TrustDatabase::new()
+                .trust_maintainer("foo".into())
+                .trust_maintainer("bar".into())
+        );
+    }
+
+    #[test]
+    fn merge_overlapping() {
+        let trustdb = TrustDatabase::new()
+            .trust_maintainer("foo".into())
+            .trust_maintainer("bar".into())
+            .trust_key("SHA256:foo".into())
+            .merge(
+                TrustDatabase::new()
+                    .trust_maintainer("bar".into())
+                    .trust_maintainer("baz".into())
+                    .trust_key("SHA256:baz".into()),
+            );
+        for maintainer in ["foo", "bar", "baz"] {
+            assert!(trustdb.is_trusted_maintainer(maintainer), "{}", maintainer);
+        }
+        assert!(!trustdb.is_trusted_maintainer("qux"));
+        assert!(trustdb.is_trusted_key("SHA256:foo"));
+        assert!(trustdb.is_trusted_key("SHA256:baz"));
+    }
+
+    #[test]
+    fn trustdb_json_roundtrip() {
+        let trustdb = TrustDatabase::new()
+            .trust_maintainer("foo".into())
+            .distrust_maintainer("bar".into())
+            .trust_key("SHA256:foo".into())
+            .trust_signer("@example.com".into())
+            .strict_maintainers(true)
+            .min_votes(Some(10));
+        let json = serde_json::to_string(&trustdb).unwrap();
+        let roundtrip: TrustDatabase = serde_json::from_str(&json).unwrap();
+        assert_eq!(roundtrip, trustdb);
+        assert_eq!(
+            serde_json::from_str::<TrustDatabase>("{}").unwrap(),
+            TrustDatabase::new()
+        );
+    }
+
+    #[test]
+    fn trustdb_json_sorted() {
+        let trustdb = ["d", "b", "e", "a", "c"]
+            .into_iter()
+            .fold(TrustDatabase::new(), |trustdb, maintainer| {
+                trustdb.trust_maintainer(maintainer.into())
+            });
+        let json = serde_json::to_value(&trustdb).unwrap();
+        assert_eq!(
+            json["trusted_maintainers"],
+            serde_json::json!(["a", "b", "c", "d", "e"])
+        );
+    }
+
+    #[test]
+    fn merge_distrust_wins() {
+        let site = TrustDatabase::new()
+            .trust_maintainer("foo".into())
+            .trust_maintainer("bar".into())
+            .min_votes(Some(10));
+        let user = TrustDatabase::new()
+            .distrust_maintainer("foo".into())
+            .require_signature(true)
+            .strict_maintainers(true)
+            .ignore_co_maintainers(true);
+        for trustdb in [site.clone().merge(user.clone()), user.merge(site)] {
+            assert!(!trustdb.is_trusted_maintainer("foo"));
+            assert!(trustdb.is_distrusted_maintainer("foo"));
+            assert!(trustdb.is_trusted_maintainer("bar"));
+            assert!(trustdb.require_signature);
+            assert!(trustdb.strict_maintainers);
+            assert!(!trustdb.ignore_co_maintainers);
+            assert_eq!(trustdb.min_votes, Some(10));
+        }
+    }
+
+    #[test]
+    fn check_all_trust_many_packages() {
+        let trustdb = TrustDatabase::new().trust_key("SHA256:key".into());
+        let packages = (0..20)
+            .rev()
+            .map(|i| {
+                let mut package = package(if i % 2 == 0 {
+                    "SHA256:key"
+                } else {
+                    "SHA256:other"
+                });
+                package.name = format!("foo{:02}", i);
+                package
+            })
+            .collect::<Vec<_>>();
+        let results = check_all_trust(&trustdb, &packages);
+        assert_eq!(
+            results
+                .iter()
+                .map(|(name, verdict)| (name.clone(), verdict.trust()))
+                .collect::<Vec<_>>(),
+            (0..20)
+                .map(|i| {
+                    let trust = if i % 2 == 0 {
+                        Trust::Trusted
+                    } else {
+                        Trust::Indeterminate
+                    };
+                    (format!("foo{:02}", i), trust)
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn dependency_name_strips_version() {
+        assert_eq!(dependency_name("git"), "git");
+        assert_eq!(dependency_name("pacman>=6"), "pacman");
+        assert_eq!(dependency_name("cargo<2"), "cargo");
+        assert_eq!(dependency_name("libalpm.so=13-64"), "libalpm.so");
+    }
+
+    #[tokio::test]
+    async fn check_trust_recursive_dependency_cycle() {
+        // foo depends on bar and glibc, bar make-depends on baz, and baz depends on foo again.
+        // glibc is not in the AUR.
+        let (url, requests) = serve(|target| {
+            let url = reqwest::Url::parse(&format!("http://localhost{}", target)).unwrap();
+            let results = url
+                .query_pairs()
+                .filter(|(key, _)| key == "arg[]")
+                .filter_map(|(_, name)| {
+                    let (maintainer, depends, make_depends) = match name.as_ref() {
+                        "foo" => ("alice", vec!["bar>=1.0", "glibc"], vec![]),
+                        "bar" => ("bob", vec![], vec!["baz"]),
+                        "baz" => ("alice", vec!["foo"], vec![]),
+                        _ => return None,
+                    };
+                    Some(serde_json::json!({
+                        "Name": name,
+                        "PackageBase": name,
+                        "PackageBaseID": 1,
+                        "OutOfDate": null,
+                        "NumVotes": 1,
+                        "Popularity": 0.0,
+                        "FirstSubmitted": 0,
+                        "LastModified": 0,
+                        "Maintainer": maintainer,
+                        "Depends": depends,
+                        "MakeDepends": make_depends,
+                    }))
+                })
+                .collect::<Vec<_>>();
+            let body = serde_json::json!({"resultcount": results.len(), "results": results});
+            json_response("200 OK", &body.to_string())
+        })
+        .await;
+        let client = AurRpcClient::with_base_url(url).unwrap();
+        let trustdb = TrustDatabase::new().trust_maintainer("alice".into());
+
+        let verdicts = check_trust_recursive(&client, &trustdb, "foo")
+            .await
+            .unwrap();
+        let mut trust = verdicts
+            .iter()
+            .map(|(name, verdict)| (name.as_str(), verdict.trust()))
+            .collect::<Vec<_>>();
+        trust.sort();
+        assert_eq!(
+            trust,
+            [
+                ("bar", Trust::Indeterminate),
+                ("baz", Trust::Trusted),
+                ("foo", Trust::Trusted),
+            ]
+        );
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    /// A fake AUR with a fixed set of packages.
+    struct FakeAur(Vec<AurPackage>);
+
+    #[async_trait]
+    impl AurInfoProvider for FakeAur {
+        async fn info(&self, packages: &[String]) -> rpc::Result<Vec<AurPackage>> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|package| packages.contains(&package.name))
+                .cloned()
+                .collect())
+        }
+    }
+
+    /// A fake AUR which is always rate-limited.
+    struct RateLimitedAur;
+
+    #[async_trait]
+    impl AurInfoProvider for RateLimitedAur {
+        async fn info(&self, _packages: &[String]) -> rpc::Result<Vec<AurPackage>> {
+            Err(rpc::AurRpcError::RateLimited {
+                retry_after: std::time::Duration::ZERO,
+            })
+        }
+    }
+
+    /// A fake AUR which takes a while to answer every lookup.
+    struct SlowAur(FakeAur, Duration);
+
+    #[async_trait]
+    impl AurInfoProvider for SlowAur {
+        async fn info(&self, packages: &[String]) -> rpc::Result<Vec<AurPackage>> {
+            tokio::time::sleep(self.1).await;
+            self.0.info(packages).await
+        }
+    }
+
+    /// Create a git repository with a single unsigned commit in a temporary directory.
+    fn unsigned_clone(name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "aur-trust-test-clone-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        for args in [
+            &["init", "--quiet", "."][..],
+            &[
+                "-c",
+                "user.name=Jane Doe",
+                "-c",
+                "user.email=jane@example.com",
+                "commit",
+                "--quiet",
+                "--allow-empty",
+                "--no-gpg-sign",
+                "-m",
+                "Commit",
+            ],
+        ] {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(&path)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+        path
+    }
+
+    fn aur_package(name: &str, maintainer: &str, depends: &[&str]) -> AurPackage {
+        serde_json::from_value(serde_json::json!({
+            "Name": name,
+            "PackageBase": name,
+            "PackageBaseID": 1,
+            "OutOfDate": null,
+            "NumVotes": 1,
+            "Popularity": 0.0,
+            "FirstSubmitted": 0,
+            "LastModified": 0,
+            "Maintainer": maintainer,
+            "Depends": depends,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn check_trust_recursive_fake_provider() {
+        let aur = FakeAur(vec![
+            aur_package("foo", "alice", &["bar", "glibc"]),
+            aur_package("bar", "mallory", &[]),
+        ]);
+        let trustdb = TrustDatabase::new()
+            .trust_maintainer("alice".into())
+            .distrust_maintainer("mallory".into());
+        let verdicts = check_trust_recursive(&aur, &trustdb, "foo").await.unwrap();
+        assert_eq!(verdicts.len(), 2);
+        assert_eq!(verdicts["foo"].trust(), Trust::Trusted);
+        assert_eq!(verdicts["bar"].trust(), Trust::Untrusted);
+    }
+
+    #[tokio::test]
+    async fn check_trust_recursive_stream_matches_batch() {
+        // foo and baz depend on each other
+        let aur = FakeAur(vec![
+            aur_package("foo", "alice", &["bar", "baz>=2", "glibc"]),
+            aur_package("bar", "mallory", &["baz"]),
+            aur_package("baz", "bob", &["foo"]),
+        ]);
+        let trustdb = TrustDatabase::new()
+            .trust_maintainer("alice".into())
+            .distrust_maintainer("mallory".into());
+        let streamed = check_trust_recursive_stream(&aur, &trustdb, "foo")
+            .map(|(name, verdict)| (name, verdict.unwrap()))
+            .collect::<Vec<_>>()
+            .await;
+        let names = streamed
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["foo", "bar", "baz"]);
+        assert_eq!(
+            streamed.into_iter().collect::<HashMap<_, _>>(),
+            check_trust_recursive(&aur, &trustdb, "foo").await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn check_dependency_closure_shared_dependency() {
+        // bar and baz both depend on qux, and qux depends back on foo
+        let aur = FakeAur(vec![
+            aur_package("foo", "alice", &["bar", "baz", "glibc"]),
+            aur_package("bar", "alice", &["qux"]),
+            aur_package("baz", "bob", &["qux>=1"]),
+            aur_package("qux", "mallory", &["foo"]),
+            aur_package("unrelated", "mallory", &[]),
+        ]);
+        let trustdb = TrustDatabase::new()
+            .trust_maintainer("alice".into())
+            .distrust_maintainer("mallory".into());
+        let (verdicts, verdict) = check_dependency_closure(&aur, &trustdb, "foo")
+            .await
+            .unwrap();
+        let mut names = verdicts.keys().map(String::as_str).collect::<Vec<_>>();
+        names.sort_unstable();
+        assert_eq!(names, ["bar", "baz", "foo", "qux"]);
+        assert_eq!(verdicts["foo"].trust(), Trust::Trusted);
+        assert_eq!(verdicts["baz"].trust(), Trust::Indeterminate);
+        assert_eq!(verdict, verdicts["qux"]);
+        assert_eq!(verdict.trust(), Trust::Untrusted);
+    }
+
+    #[tokio::test]
+    async fn check_dependency_closure_unknown_root() {
+        let aur = FakeAur(vec![aur_package("foo", "alice", &[])]);
+        let (verdicts, verdict) = check_dependency_closure(&aur, &TrustDatabase::new(), "bar")
+            .await
+            .unwrap();
+        assert!(verdicts.is_empty());
+        assert_eq!(verdict, TrustVerdict::default());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn check_trust_recursive_stream_with_budget_times_out() {
+        let aur = SlowAur(
+            FakeAur(vec![
+                aur_package("foo", "alice", &["bar"]),
+                aur_package("bar", "bob", &["baz"]),
+                aur_package("baz", "bob", &[]),
+            ]),
+            Duration::from_secs(2),
+        );
+        let trustdb = TrustDatabase::new().trust_maintainer("alice".into());
+        let verdicts =
+            check_trust_recursive_stream_with_budget(&aur, &trustdb, "foo", Duration::from_secs(3))
+                .map(|(name, verdict)| (name, verdict.unwrap()))
+                .collect::<Vec<_>>()
+                .await;
+        assert_eq!(
+            verdicts,
+            vec![
+                (
+                    "foo".to_string(),
+                    TrustVerdict::new(Trust::Trusted)
+                        .add_reason(TrustReason::AllMaintainersTrusted)
+                ),
+                (
+                    "bar".to_string(),
+                    TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::TimedOut)
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn check_trust_recursive_stream_with_budget_in_time() {
+        let aur = SlowAur(
+            FakeAur(vec![
+                aur_package("foo", "alice", &["bar"]),
+                aur_package("bar", "bob", &[]),
+            ]),
+            Duration::from_secs(2),
+        );
+        let trustdb = TrustDatabase::new().trust_maintainer("alice".into());
+        let verdicts =
+            check_trust_recursive_stream_with_budget(&aur, &trustdb, "foo", Duration::from_secs(5))
+                .map(|(name, verdict)| (name, verdict.unwrap()))
+                .collect::<HashMap<_, _>>()
+                .await;
+        assert_eq!(
+            verdicts,
+            check_trust_recursive(&aur, &trustdb, "foo").await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn check_cloned_packages_verdicts_and_git_errors() {
+        let clone = unsigned_clone("verdicts");
+        let aur = FakeAur(vec![aur_package("foo", "alice", &[])]);
+        let trustdb = TrustDatabase::new().trust_maintainer("alice".into());
+        let clones = [
+            ("foo".to_string(), clone.clone()),
+            ("bar".to_string(), clone.join("does-not-exist")),
+        ];
+        let results = check_cloned_packages(&aur, &trustdb, &clones).await;
+        std::fs::remove_dir_all(&clone).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "bar");
+        assert!(
+            matches!(results[0].1, Err(CheckError::Git(_))),
+            "{:?}",
+            results[0].1
+        );
+        assert_eq!(results[1].0, "foo");
+        assert_eq!(results[1].1.as_ref().unwrap().trust(), Trust::Trusted);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn check_cloned_packages_with_budget_times_out() {
+        let clone = unsigned_clone("budget");
+        // Every lookup takes two seconds, and with a budget of three seconds only the first
+        // CHECK_CONCURRENCY lookups finish in time
+        let aur = SlowAur(FakeAur(Vec::new()), Duration::from_secs(2));
+        let clones = (0..CHECK_CONCURRENCY + 2)
+            .map(|i| (format!("package{:02}", i), clone.clone()))
+            .collect::<Vec<_>>();
+        let results = check_cloned_packages_with_budget(
+            &aur,
+            &TrustDatabase::new(),
+            &clones,
+            Duration::from_secs(3),
+        )
+        .await;
+        std::fs::remove_dir_all(&clone).unwrap();
+        let names = results
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        let expected_names = clones
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, expected_names);
+        for (name, result) in &results[..CHECK_CONCURRENCY] {
+            let verdict = result.as_ref().unwrap();
+            assert!(
+                verdict.reasons().contains(&TrustReason::MaintainersUnknown),
+                "{}: {:?}",
+                name,
+                verdict
+            );
+            assert!(!verdict.reasons().contains(&TrustReason::TimedOut));
+        }
+        for (name, result) in &results[CHECK_CONCURRENCY..] {
+            assert_eq!(result.as_ref().unwrap(), &timed_out(), "{}", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn check_cloned_packages_aur_error() {
+        let clone = unsigned_clone("aur-error");
+        let clones = [("foo".to_string(), clone.clone())];
+        let results = check_cloned_packages(&RateLimitedAur, &TrustDatabase::new(), &clones).await;
+        std::fs::remove_dir_all(&clone).unwrap();
+        assert!(
+            matches!(
+                results[0].1,
+                Err(CheckError::Aur(rpc::AurRpcError::RateLimited { .. }))
+            ),
+            "{:?}",
+            results[0].1
+        );
+    }
+
+    #[test]
+    fn check_error_from_git_error() {
+        assert!(matches!(
+            CheckError::from(GitError::UnknownSignature("garbage".into())),
+            CheckError::Signature(GitError::UnknownSignature(_))
+        ));
+        assert!(matches!(
+            CheckError::from(GitError::Io(std::io::ErrorKind::NotFound.into())),
+            CheckError::Git(GitError::Io(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn check_trust_by_name_fake_provider() {
+        let aur = FakeAur(vec![aur_package("foo", "alice", &[])]);
+        let trustdb = TrustDatabase::new().trust_maintainer("alice".into());
+        let unsigned = GitCommit::new("ae5a1b0".try_into().unwrap(), None);
+
+        let verdict = check_trust_by_name(&aur, &trustdb, "foo", unsigned.clone())
+            .await
+            .unwrap();
+        assert_eq!(verdict.trust(), Trust::Trusted);
+        assert!(verdict
+            .reasons()
+            .contains(&TrustReason::AllMaintainersTrusted));
+
+        let verdict = check_trust_by_name(&aur, &trustdb, "bar", unsigned)
+            .await
+            .unwrap();
+        assert_eq!(verdict.trust(), Trust::Indeterminate);
+        assert!(verdict.reasons().contains(&TrustReason::MaintainersUnknown));
+    }
+
+    #[test]
+    #[traced_test]
+    fn check_trust_span() {
+        let mut package = package("SHA256:key");
+        package.name = "aur-trust-span-test".into();
+        check_trust(&TrustDatabase::new(), &package);
+        assert!(logs_contain(
+            "check_trust{policy=Either package=aur-trust-span-test}"
+        ));
+        assert!(logs_contain("Signature of commit ae5a1b0: indeterminate"));
+        assert!(logs_contain("Maintainers: indeterminate"));
+    }
+
+    #[test]
+    fn check_trust_report_sub_verdicts() {
+        let mut package = package("SHA256:key");
+        package.maintainers.insert("bar".into());
+        let trustdb = TrustDatabase::new().trust_key("SHA256:key".into());
+        let report = check_trust_report(&trustdb, &package);
+        assert_eq!(report.package(), "foo");
+        assert_eq!(
+            report.commit_verdict(),
+            &check_commit_signature(&trustdb, &package.head_commit)
+        );
+        assert_eq!(
+            report.maintainer_verdict(),
+            &check_maintainers(&trustdb, &package.maintainers)
+        );
+        assert_eq!(report.commit_verdict().trust(), Trust::Trusted);
+        assert_eq!(report.maintainer_verdict().trust(), Trust::Indeterminate);
+        assert_eq!(report.verdict(), &check_trust(&trustdb, &package));
+        assert_eq!(report.verdict().trust(), Trust::Trusted);
+    }
+
+    #[test]
+    fn check_trust_with_policy_combinations() {
+        let trustdb = TrustDatabase::new()
+            .trust_key("SHA256:trusted".into())
+            .trust_maintainer("trusted".into())
+            .distrust_maintainer("distrusted".into());
+        let signatures = [
+            (
+                Some(SignatureValidity::Good),
+                "SHA256:trusted",
+                Trust::Trusted,
+            ),
+            (
+                Some(SignatureValidity::Good),
+                "SHA256:other",
+                Trust::Indeterminate,
+            ),
+            (None, "SHA256:trusted", Trust::Indeterminate),
+            (
+                Some(SignatureValidity::BadSignature),
+                "SHA256:trusted",
+                Trust::Untrusted,
+            ),
+        ];
+        let maintainers = [
+            ("trusted", Trust::Trusted),
+            ("other", Trust::Indeterminate),
+            ("distrusted", Trust::Untrusted),
+        ];
+        for (validity, key, signature_trust) in signatures {
+            for (maintainer, maintainer_trust) in maintainers {
+                let mut package = package(key);
+                package.head_commit.signature = validity.map(|validity| CommitSignature {
+                    validity,
+                    signer: "Jane Doe <jane@example.com>".into(),
+                    key_id: key.into(),
+                    fingerprint: None,
+                    algorithm: None,
+                    key_expiry: None,
+                    sig_timestamp: None,
+                });
+                package.maintainers.insert(maintainer.into());
+                let either = check_trust_with_policy(&trustdb, &package, TrustPolicy::Either);
+                let both = check_trust_with_policy(&trustdb, &package, TrustPolicy::Both);
+                assert_eq!(either, check_trust(&trustdb, &package));
+                assert_eq!(
+                    either.trust(),
+                    signature_trust.max(maintainer_trust),
+                    "{:?}",
+                    package
+                );
+                assert_eq!(
+                    both.trust(),
+                    signature_trust.min(maintainer_trust),
+                    "{:?}",
+                    package
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn check_trust_strict_maintainers() {
+        let mut package = package("SHA256:key");
+        package.maintainers.insert("bar".into());
+        let trustdb = TrustDatabase::new().trust_key("SHA256:key".into());
+        assert_eq!(check_trust(&trustdb, &package).trust(), Trust::Trusted);
+
+        let trustdb = trustdb.strict_maintainers(true);
+        assert_eq!(
+            check_trust(&trustdb, &package),
+            TrustVerdict::new(Trust::Untrusted).add_reason(TrustReason::MaintainerNotTrusted {
+                maintainer: "bar".into()
+            })
+        );
+    }
+
+    #[test]
+    fn check_trust_min_votes() {
+        let mut package = package("SHA256:key");
+        package.num_votes = Some(2);
+        let trustdb = TrustDatabase::new().trust_key("SHA256:key".into());
+        assert_eq!(check_trust(&trustdb, &package).trust(), Trust::Trusted);
+
+        let trustdb = trustdb.min_votes(Some(10));
+        assert_eq!(
+            check_trust(&trustdb, &package),
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::NotEnoughVotes {
+                votes: 2,
+                min_votes: 10
+            })
+        );
+
+        package.num_votes = Some(10);
+        assert_eq!(check_trust(&trustdb, &package).trust(), Trust::Trusted);
+        package.num_votes = None;
+        assert_eq!(check_trust(&trustdb, &package).trust(), Trust::Trusted);
+    }
+
+    #[test]
+    fn check_trust_unsigned_trusted_maintainer() {
+        let mut package = package("SHA256:key");
+        package.head_commit.signature = None;
+        package.maintainers.insert("bar".into());
+        let trustdb = TrustDatabase::new().trust_maintainer("bar".into());
+        assert_eq!(check_trust(&trustdb, &package).trust(), Trust::Trusted);
+
+        let trustdb = trustdb.require_signature(true);
+        assert_eq!(
+            check_trust(&trustdb, &package),
+            TrustVerdict::new(Trust::Untrusted)
+                .add_reason(TrustReason::NoSignature {
+                    sha1: "ae5a1b0".into()
+                })
+                .add_reason(TrustReason::SignatureRequired)
+        );
+    }
+
+    #[test]
+    fn check_trust_ignore_co_maintainers() {
+        let mut package = package("SHA256:key");
+        package.head_commit.signature = None;
+        package.maintainers = HashSet::from(["foo".into(), "bar".into()]);
+        package.primary_maintainer = Some("foo".into());
+        let trustdb = TrustDatabase::new().trust_maintainer("foo".into());
+        assert_eq!(
+            check_trust(&trustdb, &package).trust(),
+            Trust::Indeterminate
+        );
+
+        let trustdb = trustdb.ignore_co_maintainers(true);
+        assert_eq!(
+            check_trust(&trustdb, &package),
+            TrustVerdict::new(Trust::Trusted)
+                .add_reason(TrustReason::AllMaintainersTrusted)
+                .add_reason(TrustReason::CoMaintainerIgnored {
+                    maintainer: "bar".into()
+                })
+        );
+    }
+
+    #[test]
+    fn validate_well_formed_keys() {
+        let trustdb = TrustDatabase::new()
+            .trust_key("SHA256:nThbg6kXUpJWGl7E1IGOCspRomTxdCARLviKw6E5SY8".into())
+            .trust_key("B8BC8C7DE9F5E6E1".into())
+            .trust_key("0123456789ABCDEF0123456789abcdef01234567".into());
+        assert_eq!(trustdb.validate(), Vec::new());
+    }
+
+    #[test]
+    fn validate_malformed_keys() {
+        let trustdb = TrustDatabase::new()
+            .trust_key("SHA256:nThbg6kXUpJWGl7E1IGOCspRomTxdCARLviKw6E5SY8".into())
+            .trust_key("nThbg6kXUpJWGl7E1IGOCspRomTxdCARLviKw6E5SY8".into())
+            .trust_key("SHA256:tooshort".into())
+            .trust_key("B8BC8C7DE9F5E6E".into());
+        assert_eq!(
+            trustdb.validate(),
+            vec![
+                ValidationWarning::MalformedKey("B8BC8C7DE9F5E6E".into()),
+                ValidationWarning::MalformedKey("SHA256:tooshort".into()),
+                ValidationWarning::MalformedKey(
+                    "nThbg6kXUpJWGl7E1IGOCspRomTxdCARLviKw6E5SY8".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn tofu_first_use() {
+        let mut store = TofuStore::new();
+        let verdict = check_commit_signature_tofu(&mut store, &package("SHA256:key"));
+        assert_eq!(verdict.trust(), Trust::Trusted);
+        assert_eq!(store.get("foo"), Some(&"SHA256:key".to_string()));
+
+        let verdict = check_commit_signature_tofu(&mut store, &package("SHA256:key"));
+        assert_eq!(verdict.trust(), Trust::Trusted);
+    }
+
+    #[test]
+    fn tofu_key_changed() {
+        let mut store = TofuStore::new();
+        check_commit_signature_tofu(&mut store, &package("SHA256:key"));
+        let verdict = check_commit_signature_tofu(&mut store, &package("SHA256:other"));
+        assert_eq!(verdict.trust(), Trust::Untrusted);
+        assert_eq!(
+            verdict.reasons(),
+            [TrustReason::KeyChangedSinceFirstUse {
+                sha1: "ae5a1b0".into(),
+                package: "foo".into(),
+                key: "SHA256:other".into(),
+                first_key: "SHA256:key".into(),
+            }]
+        );
+        assert_eq!(store.get("foo"), Some(&"SHA256:key".to_string()));
+    }
+
+    #[test]
+    fn tofu_by_package_base() {
+        let mut store = TofuStore::new();
+        let mut foo = package("SHA256:key");
+        foo.package_base = Some("foo-base".into());
+        check_commit_signature_tofu(&mut store, &foo);
+        assert_eq!(store.get("foo-base"), Some(&"SHA256:key".to_string()));
+        assert_eq!(store.get("foo"), None);
+
+        let mut bar = package("SHA256:key");
+        bar.name = "bar".into();
+        bar.package_base = Some("foo-base".into());
+        let verdict = check_commit_signature_tofu(&mut store, &bar);
+        assert_eq!(verdict.trust(), Trust::Trusted);
+
+        bar.head_commit.signature.as_mut().unwrap().key_id = "SHA256:other".into();
+        let verdict = check_commit_signature_tofu(&mut store, &bar);
+        assert_eq!(verdict.trust(), Trust::Untrusted);
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn tofu_fingerprint_then_key_id() {
+        let mut store = TofuStore::new();
+        let mut first = package("B8BC8C7DE9F5E6E1");
+        first.head_commit.signature.as_mut().unwrap().fingerprint =
+            Some("0123456789ABCDEF01234567B8BC8C7DE9F5E6E1".into());
+        check_commit_signature_tofu(&mut store, &first);
+        assert_eq!(
+            store.get("foo"),
+            Some(&"0123456789ABCDEF01234567B8BC8C7DE9F5E6E1".to_string())
+        );
+
+        let verdict = check_commit_signature_tofu(&mut store, &package("b8bc8c7de9f5e6e1"));
+        assert_eq!(verdict.trust(), Trust::Trusted);
+        let verdict = check_commit_signature_tofu(&mut store, &package("01234567B8BC8C7D"));
+        assert_eq!(verdict.trust(), Trust::Untrusted);
+    }
+
+    #[test]
+    fn tofu_ignores_bad_signature() {
+        let mut store = TofuStore::new();
+        let mut package = package("SHA256:key");
+        package.head_commit.signature.as_mut().unwrap().validity = SignatureValidity::BadSignature;
+        let verdict = check_commit_signature_tofu(&mut store, &package);
+        assert_eq!(verdict.trust(), Trust::Untrusted);
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn combined_verdict_empty() {
+        assert_eq!(combined_verdict(Vec::new()), TrustVerdict::default());
+    }
+
+    #[test]
+    fn combined_verdict_meet() {
+        let verdicts = vec![
+            TrustVerdict::new(Trust::Trusted).add_reason(TrustReason::AllMaintainersTrusted),
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::MaintainersUnknown),
+            TrustVerdict::new(Trust::Trusted).add_reason(TrustReason::AllMaintainersTrusted),
+        ];
+        assert_eq!(
+            combined_verdict(verdicts),
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::MaintainersUnknown)
+        );
+    }
+
+    #[test]
+    fn combined_verdict_stops_at_untrusted() {
+        let untrusted =
+            TrustVerdict::new(Trust::Untrusted).add_reason(TrustReason::MaintainerDistrusted {
+                maintainer: "bar".into(),
+            });
+        let verdicts = [
+            TrustVerdict::new(Trust::Trusted),
+            TrustVerdict::new(Trust::Indeterminate),
+            untrusted.clone(),
+        ]
+        .into_iter()
+        .chain(std::iter::from_fn(|| -> Option<TrustVerdict> {
+            panic!("Verdicts consumed after untrusted verdict")
+        }));
+        assert_eq!(combined_verdict(verdicts), untrusted);
+    }
+
+    #[test]
+    fn combined_verdict_keeps_reasons_of_first_untrusted() {
+        let distrusted = |maintainer: &str| {
+            TrustVerdict::new(Trust::Untrusted).add_reason(TrustReason::MaintainerDistrusted {
+                maintainer: maintainer.into(),
+            })
+        };
+        let verdicts = [
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::MaintainersUnknown),
+            distrusted("foo"),
+            distrusted("bar"),
+        ];
+        assert_eq!(combined_verdict(verdicts), distrusted("foo"));
+    }
+
+    #[test]
+    fn combined_verdict_explained_has_all_verdicts() {
+        let verdicts = vec![
+            TrustVerdict::new(Trust::Trusted).add_reason(TrustReason::AllMaintainersTrusted),
+            TrustVerdict::new(Trust::Untrusted).add_reason(TrustReason::MaintainerDistrusted {
+                maintainer: "bar".into(),
+            }),
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::MaintainersUnknown),
+        ];
+        let (verdict, explanation) = combined_verdict_explained(verdicts.clone());
+        assert_eq!(verdict, combined_verdict(verdicts));
+        assert_eq!(
+            explanation,
+            vec![
+                (Trust::Trusted, vec!["All maintainers trusted".to_string()]),
+                (
+                    Trust::Untrusted,
+                    vec!["Maintainer bar is distrusted".to_string()]
+                ),
+                (
+                    Trust::Indeterminate,
+                    vec!["Maintainers unknown".to_string()]
+                ),
+            ]
+        );
+    }
+
+    #[quickcheck]
+    fn combined_verdict_single_verdict_is_identity(verdict: TrustVerdict) -> bool {
+        combined_verdict(vec![verdict.clone()]) == verdict
+    }
+
+    #[quickcheck]
+    fn combined_verdict_is_meet_up_to_first_untrusted(verdicts: Vec<TrustVerdict>) -> bool {
+        let folded = verdicts
+            .iter()
+            .cloned()
+            .fold(TrustVerdict::top(), MeetSemiLattice::meet);
+        let until_untrusted = match verdicts
+            .iter()
+            .position(|verdict| verdict.trust() == Trust::Untrusted)
+        {
+            Some(index) => &verdicts[..=index],
+            None => &verdicts[..],
+        };
+        let folded_until_untrusted = until_untrusted
+            .iter()
+            .cloned()
+            .fold(TrustVerdict::top(), MeetSemiLattice::meet);
+        let combined = combined_verdict(verdicts.clone());
+        verdicts.is_empty()
+            || (combined.trust() == folded.trust() && combined == folded_until_untrusted)
+    }
+
+    #[quickcheck]
+    fn combined_verdict_ignores_order(verdicts: Vec<TrustVerdict>, seed: Vec<usize>) -> bool {
+        // Shuffle verdicts by swapping every verdict with another one chosen by the seed
+        let mut permuted = verdicts.clone();
+        if !seed.is_empty() {
+            for i in 0..permuted.len() {
+                let j = seed[i % seed.len()] % permuted.len();
+                permuted.swap(i, j);
+            }
+        }
+        let sorted_reasons = |verdict: &TrustVerdict| {
+            let mut reasons = verdict.reasons().to_vec();
+            reasons.sort();
+            reasons
+        };
+        let combined = combined_verdict(verdicts);
+        let combined_permuted = combined_verdict(permuted);
+        // The reasons of an untrusted verdict come from whichever untrusted verdict is first
+        combined.trust() == combined_permuted.trust()
+            && (combined.trust() == Trust::Untrusted
+                || sorted_reasons(&combined) == sorted_reasons(&combined_permuted))
+    }
+
+    #[test]
+    fn group_reasons_by_maintainer() {
+        let trustdb = TrustDatabase::new().trust_maintainer("foo".into());
+        let results = ["a", "b", "c"]
+            .into_iter()
+            .map(|name| {
+                let maintainers = HashSet::from(["foo".into(), "bar".into()]);
+                (name.to_string(), check_maintainers(&trustdb, &maintainers))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            group_reasons(&results),
+            vec![(
+                TrustReason::MaintainerNotTrusted {
+                    maintainer: "bar".into()
+                },
+                vec!["a".to_string(), "b".to_string(), "c".to_string()]
+            )]
+        );
+    }
+
+    #[test]
+    fn summarize_mixed_verdicts() {
+        let verdicts = [
+            TrustVerdict::new(Trust::Trusted),
+            TrustVerdict::new(Trust::Untrusted),
+            TrustVerdict::new(Trust::Trusted),
+            TrustVerdict::new(Trust::Indeterminate),
+            TrustVerdict::new(Trust::Trusted),
+        ];
+        let summary = summarize(&verdicts);
+        assert_eq!(
+            summary,
+            TrustSummary {
+                trusted: 3,
+                indeterminate: 1,
+                untrusted: 1,
+            }
+        );
+        assert_eq!(
+            summary.to_string(),
+            "3 trusted, 1 indeterminate, 1 untrusted"
+        );
+        assert_eq!(summarize(&[]), TrustSummary::default());
+    }
+}
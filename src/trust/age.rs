@@ -0,0 +1,102 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Lower trust in packages submitted recently.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::aur::rpc::AurPackage;
+use crate::lattice::HasTop;
+
+use super::{Trust, TrustReason, TrustVerdict};
+
+/// Check whether `package` was first submitted to the AUR at least `min_age` ago.
+///
+/// A younger package gets an indeterminate verdict, and an older package gets the
+/// [top verdict](TrustVerdict::top).
+pub fn check_package_age(package: &AurPackage, min_age: Duration) -> TrustVerdict {
+    check_age(package.first_submitted, SystemTime::now(), min_age)
+}
+
+/// Check whether `first_submitted` lies at least `min_age` before `now`.
+///
+/// A `first_submitted` timestamp after `now`, e.g. due to clock skew, counts as age zero.
+fn check_age(first_submitted: i64, now: SystemTime, min_age: Duration) -> TrustVerdict {
+    let submitted = UNIX_EPOCH + Duration::from_secs(first_submitted.max(0).unsigned_abs());
+    let age = now.duration_since(submitted).unwrap_or_default();
+    if age < min_age {
+        TrustVerdict::new(Trust::Indeterminate)
+            .add_reason(TrustReason::RecentlySubmitted { age, min_age })
+    } else {
+        TrustVerdict::top()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aur::mock::package_1password;
+    use pretty_assertions::assert_eq;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn submitted_ago(age: Duration) -> i64 {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        (now - age).as_secs() as i64
+    }
+
+    fn package(first_submitted: i64) -> AurPackage {
+        let mut package = package_1password();
+        package.first_submitted = first_submitted;
+        package
+    }
+
+    #[test]
+    fn old_package() {
+        assert_eq!(
+            check_package_age(&package(submitted_ago(3 * 365 * DAY)), 30 * DAY),
+            TrustVerdict::new(Trust::Trusted)
+        );
+    }
+
+    #[test]
+    fn new_package() {
+        let verdict = check_package_age(&package(submitted_ago(DAY)), 30 * DAY);
+        assert_eq!(verdict.trust(), Trust::Indeterminate);
+        assert!(matches!(
+            verdict.reasons(),
+            [TrustReason::RecentlySubmitted { min_age, .. }] if *min_age == 30 * DAY
+        ));
+    }
+
+    #[test]
+    fn check_age_reason() {
+        let now = UNIX_EPOCH + 100 * DAY;
+        let verdict = check_age((99 * DAY).as_secs() as i64, now, 7 * DAY);
+        assert_eq!(
+            verdict,
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::RecentlySubmitted {
+                age: DAY,
+                min_age: 7 * DAY
+            })
+        );
+        assert_eq!(
+            verdict.reasons()[0].to_string(),
+            "Package was first submitted only 1 day ago, less than 7 days ago"
+        );
+        assert_eq!(
+            check_age((93 * DAY).as_secs() as i64, now, 7 * DAY),
+            TrustVerdict::new(Trust::Trusted)
+        );
+    }
+
+    #[test]
+    fn check_age_in_future() {
+        let now = UNIX_EPOCH + 100 * DAY;
+        assert_eq!(
+            check_age((101 * DAY).as_secs() as i64, now, 7 * DAY).trust(),
+            Trust::Indeterminate
+        );
+    }
+}
@@ -0,0 +1,72 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Export trust verdicts as in-toto attestations.
+
+use serde_json::json;
+
+use super::{PackageWithEvidence, TrustVerdict};
+
+/// The in-toto statement type.
+static STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+
+/// The predicate type of trust verdicts.
+static PREDICATE_TYPE: &str = "https://github.com/swsnr/aur-trust/verdict/v1";
+
+/// Create an in-toto attestation statement for the `verdict` on `package`.
+///
+/// The subject of the statement is the package, with the HEAD commit as `gitCommit` digest, and
+/// the predicate holds the trust and the human-readable reasons of the verdict.
+///
+/// Note that the digest holds the abbreviated SHA1 of the HEAD commit, because that's all we
+/// know about the commit.
+pub fn to_attestation(package: &PackageWithEvidence, verdict: &TrustVerdict) -> serde_json::Value {
+    json!({
+        "_type": STATEMENT_TYPE,
+        "subject": [{
+            "name": package.name,
+            "digest": { "gitCommit": package.head_commit.abbrev_sha1 },
+        }],
+        "predicateType": PREDICATE_TYPE,
+        "predicate": {
+            "trust": verdict.trust(),
+            "reasons": verdict.reasons().iter().map(ToString::to_string).collect::<Vec<_>>(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::trust::{GitCommit, Trust, TrustReason};
+    use pretty_assertions::assert_eq;
+    use std::collections::HashSet;
+
+    #[test]
+    fn attestation() {
+        let package = PackageWithEvidence {
+            name: "foo".into(),
+            package_base: None,
+            maintainers: HashSet::new(),
+            primary_maintainer: None,
+            head_commit: GitCommit {
+                abbrev_sha1: "ae5a1b0".try_into().unwrap(),
+                signature: None,
+            },
+            num_votes: None,
+        };
+        let verdict =
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::NoSignature {
+                sha1: "ae5a1b0".into(),
+            });
+        let attestation = to_attestation(&package, &verdict);
+        assert_eq!(attestation["subject"][0]["name"], "foo");
+        assert_eq!(attestation["subject"][0]["digest"]["gitCommit"], "ae5a1b0");
+        assert_eq!(attestation["predicate"]["trust"], "indeterminate");
+        assert_eq!(
+            attestation["predicate"]["reasons"],
+            json!(["Commit ae5a1b0 is not signed"])
+        );
+    }
+}
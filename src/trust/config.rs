@@ -0,0 +1,340 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Load trust databases from configuration files.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::TrustDatabase;
+
+/// Loading a trust configuration failed.
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    /// Reading the configuration file failed.
+    #[error("failed to read configuration")]
+    Io(#[from] std::io::Error),
+    /// Parsing the configuration failed.
+    #[error("failed to parse configuration")]
+    Toml(#[from] toml::de::Error),
+}
+
+/// A trust configuration file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    #[serde(default)]
+    trust: TrustConfig,
+}
+
+/// The `[trust]` table of a trust configuration file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TrustConfig {
+    /// Trusted maintainers.
+    #[serde(default)]
+    maintainers: HashSet<String>,
+    /// Distrusted maintainers.
+    #[serde(default)]
+    distrusted_maintainers: HashSet<String>,
+    /// Trusted signing keys.
+    #[serde(default)]
+    keys: HashSet<String>,
+    /// Trusted signers.
+    #[serde(default)]
+    signers: HashSet<String>,
+    /// Signer patterns by maintainer.
+    #[serde(default)]
+    maintainer_signers: BTreeMap<String, BTreeSet<String>>,
+    /// Whether to require signatures.
+    require_signature: Option<bool>,
+    /// Whether to ignore co-maintainers.
+    ignore_co_maintainers: Option<bool>,
+    /// Whether to distrust packages with untrusted maintainers.
+    strict_maintainers: Option<bool>,
+    /// The minimum number of votes.
+    min_votes: Option<u32>,
+}
+
+/// A source of trust configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// A TOML configuration file at the given path.
+    File(PathBuf),
+    /// An inline TOML configuration, e.g. from an environment variable.
+    Inline(String),
+}
+
+impl ConfigSource {
+    /// Load a trust database from this source.
+    pub fn load(&self) -> Result<TrustDatabase, ConfigError> {
+        let mut trustdb = TrustDatabase::default();
+        trustdb.apply_config(self.read()?.trust);
+        Ok(trustdb)
+    }
+
+    /// Read the configuration from this source.
+    fn read(&self) -> Result<Config, ConfigError> {
+        match self {
+            ConfigSource::File(path) => Ok(toml::from_str(&std::fs::read_to_string(path)?)?),
+            ConfigSource::Inline(s) => Ok(toml::from_str(s)?),
+        }
+    }
+}
+
+impl TrustDatabase {
+    /// Load a trust database from a TOML configuration in `s`.
+    ///
+    /// The configuration has a `[trust]` table with the following keys, all optional:
+    ///
+    /// - `maintainers`, an array of trusted maintainers,
+    /// - `distrusted_maintainers`, an array of distrusted maintainers,
+    /// - `keys`, an array of trusted signing keys, see [`TrustDatabase::trust_key`],
+    /// - `signers`, an array of trusted signers, see [`TrustDatabase::trust_signer`],
+    /// - `maintainer_signers`, a table of signer patterns by maintainer, see
+    ///   [`TrustDatabase::maintainer_signer`],
+    /// - `require_signature`, `ignore_co_maintainers` and `strict_maintainers`, booleans for
+    ///   the corresponding settings of the trust database, and
+    /// - `min_votes`, the minimum number of votes, see [`TrustDatabase::min_votes`].
+    ///
+    /// A maintainer both trusted and distrusted is distrusted.  Unknown keys are rejected.
+    pub fn from_toml_str(s: &str) -> Result<Self, ConfigError> {
+        ConfigSource::Inline(s.to_string()).load()
+    }
+
+    /// Load a trust database from the TOML configuration file at `path`.
+    ///
+    /// See [`TrustDatabase::from_toml_str`] for the format.
+    pub fn from_toml_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        ConfigSource::File(path.as_ref().to_path_buf()).load()
+    }
+
+    /// Load a trust database from multiple configuration `sources`.
+    ///
+    /// Apply all sources in order, e.g. a system configuration first, then a user configuration,
+    /// and finally overrides from the environment, so that later sources take precedence:
+    ///
+    /// - A later source which trusts a maintainer lifts the distrust of an earlier source, and
+    ///   a later source which distrusts a maintainer revokes the trust of an earlier source.
+    ///   Within a single source distrust wins.
+    /// - Trusted keys, trusted signers and signers of maintainers add up across all sources.
+    /// - A later source which sets `require_signature`, `ignore_co_maintainers`,
+    ///   `strict_maintainers` or `min_votes` overrides earlier sources; sources which omit a
+    ///   setting leave it as it is.
+    ///
+    /// Fail if any source fails to load, including missing files; leave out optional sources
+    /// which do not exist.  Without any sources return an empty trust database.
+    pub fn from_layered(sources: &[ConfigSource]) -> Result<Self, ConfigError> {
+        let mut trustdb = Self::default();
+        for source in sources {
+            trustdb.apply_config(source.read()?.trust);
+        }
+        Ok(trustdb)
+    }
+
+    /// Apply the given `config` on top of this trust database.
+    ///
+    /// See [`TrustDatabase::from_layered`].
+    fn apply_config(&mut self, config: TrustConfig) {
+        for maintainer in config.maintainers {
+            self.distrusted_maintainers.remove(&maintainer);
+            self.trusted_maintainers.insert(maintainer);
+        }
+        for maintainer in config.distrusted_maintainers {
+            self.trusted_maintainers.remove(&maintainer);
+            self.distrusted_maintainers.insert(maintainer);
+        }
+        self.trusted_keys.extend(config.keys);
+        self.trusted_signers.extend(config.signers);
+        for (maintainer, patterns) in config.maintainer_signers {
+            self.maintainer_signers
+                .entry(maintainer)
+                .or_default()
+                .extend(patterns);
+        }
+        if let Some(require_signature) = config.require_signature {
+            self.require_signature = require_signature;
+        }
+        if let Some(ignore_co_maintainers) = config.ignore_co_maintainers {
+            self.ignore_co_maintainers = ignore_co_maintainers;
+        }
+        if let Some(strict_maintainers) = config.strict_maintainers {
+            self.strict_maintainers = strict_maintainers;
+        }
+        if config.min_votes.is_some() {
+            self.min_votes = config.min_votes;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn valid_config() {
+        let trustdb = TrustDatabase::from_toml_str(
+            r#"[trust]
+maintainers = ["foo", "bar"]
+"#,
+        )
+        .unwrap();
+        assert!(trustdb.is_trusted_maintainer("foo"));
+        assert!(trustdb.is_trusted_maintainer("bar"));
+        assert!(!trustdb.is_trusted_maintainer("baz"));
+    }
+
+    #[test]
+    fn empty_config() {
+        let trustdb = TrustDatabase::from_toml_str("").unwrap();
+        assert!(trustdb.trusted_maintainers.is_empty());
+    }
+
+    #[test]
+    fn distrusted_maintainers() {
+        let trustdb = TrustDatabase::from_toml_str(
+            r#"[trust]
+maintainers = ["foo"]
+distrusted_maintainers = ["bar"]
+"#,
+        )
+        .unwrap();
+        assert!(trustdb.is_trusted_maintainer("foo"));
+        assert!(!trustdb.is_distrusted_maintainer("foo"));
+        assert!(trustdb.is_distrusted_maintainer("bar"));
+    }
+
+    #[test]
+    fn full_config() {
+        let trustdb = TrustDatabase::from_toml_str(
+            r#"[trust]
+maintainers = ["foo", "bar"]
+distrusted_maintainers = ["bar"]
+keys = ["SHA256:key"]
+signers = ["@example.com"]
+require_signature = true
+ignore_co_maintainers = true
+strict_maintainers = true
+min_votes = 10
+
+[trust.maintainer_signers]
+foo = ["foo@example.com"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            trustdb,
+            TrustDatabase::new()
+                .trust_maintainer("foo".into())
+                .distrust_maintainer("bar".into())
+                .trust_key("SHA256:key".into())
+                .trust_signer("@example.com".into())
+                .maintainer_signer("foo".into(), "foo@example.com".into())
+                .require_signature(true)
+                .ignore_co_maintainers(true)
+                .strict_maintainers(true)
+                .min_votes(Some(10))
+        );
+    }
+
+    #[test]
+    fn layered_user_file_overrides_system_default() {
+        let user_file = std::env::temp_dir().join(format!(
+            "aur-trust-test-layered-{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(
+            &user_file,
+            r#"[trust]
+maintainers = ["baz"]
+distrusted_maintainers = ["bar"]
+"#,
+        )
+        .unwrap();
+        let trustdb = TrustDatabase::from_layered(&[
+            ConfigSource::Inline(
+                r#"[trust]
+maintainers = ["foo", "bar"]
+"#
+                .into(),
+            ),
+            ConfigSource::File(user_file.clone()),
+        ]);
+        std::fs::remove_file(&user_file).unwrap();
+        let trustdb = trustdb.unwrap();
+        assert!(trustdb.is_trusted_maintainer("foo"));
+        assert!(trustdb.is_trusted_maintainer("baz"));
+        assert!(!trustdb.is_trusted_maintainer("bar"));
+        assert!(trustdb.is_distrusted_maintainer("bar"));
+    }
+
+    #[test]
+    fn layered_later_source_wins() {
+        let system = ConfigSource::Inline(
+            r#"[trust]
+maintainers = ["foo"]
+distrusted_maintainers = ["bar"]
+keys = ["SHA256:system"]
+require_signature = true
+min_votes = 10
+"#
+            .into(),
+        );
+        let user = ConfigSource::Inline(
+            r#"[trust]
+maintainers = ["bar"]
+distrusted_maintainers = ["foo"]
+keys = ["SHA256:user"]
+require_signature = false
+"#
+            .into(),
+        );
+        let trustdb = TrustDatabase::from_layered(&[system.clone(), user.clone()]).unwrap();
+        assert!(trustdb.is_trusted_maintainer("bar"));
+        assert!(!trustdb.is_distrusted_maintainer("bar"));
+        assert!(trustdb.is_distrusted_maintainer("foo"));
+        assert!(!trustdb.is_trusted_maintainer("foo"));
+        assert!(trustdb.is_trusted_key("SHA256:system"));
+        assert!(trustdb.is_trusted_key("SHA256:user"));
+        assert!(!trustdb.require_signature);
+        assert_eq!(trustdb.min_votes, Some(10));
+
+        // The order of layers matters
+        let trustdb = TrustDatabase::from_layered(&[user, system]).unwrap();
+        assert!(trustdb.is_trusted_maintainer("foo"));
+        assert!(trustdb.is_distrusted_maintainer("bar"));
+        assert!(trustdb.require_signature);
+    }
+
+    #[test]
+    fn layered_without_sources() {
+        assert_eq!(
+            TrustDatabase::from_layered(&[]).unwrap(),
+            TrustDatabase::new()
+        );
+    }
+
+    #[test]
+    fn layered_missing_file() {
+        let error = TrustDatabase::from_layered(&[ConfigSource::File(
+            "/does/not/exist/aur-trust.toml".into(),
+        )])
+        .unwrap_err();
+        assert!(matches!(error, ConfigError::Io(_)), "{:?}", error);
+    }
+
+    #[test]
+    fn misspelled_key() {
+        let error = TrustDatabase::from_toml_str(
+            r#"[trust]
+maintainer = ["foo"]
+"#,
+        )
+        .unwrap_err();
+        assert!(matches!(error, ConfigError::Toml(_)), "{:?}", error);
+    }
+}
@@ -3,3 +3,229 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 //! Determine package trust by checking whether package maintainers are trusted.
+
+use std::collections::HashSet;
+
+use crate::aur::rpc::AurPackage;
+use crate::lattice::HasTop;
+
+use super::{MaintainerStatus, Trust, TrustDatabase, TrustReason, TrustVerdict};
+
+/// Classify every maintainer in `maintainers` according to `trustdb`.
+///
+/// Return the status of each maintainer, sorted by maintainer name.  A distrusted maintainer
+/// is [`MaintainerStatus::Distrusted`] even if also trusted.
+pub fn classify_maintainers(
+    trustdb: &TrustDatabase,
+    maintainers: &HashSet<String>,
+) -> Vec<(String, MaintainerStatus)> {
+    let mut statuses = maintainers
+        .iter()
+        .map(|m| {
+            let status = if trustdb.is_distrusted_maintainer(m) {
+                MaintainerStatus::Distrusted
+            } else if trustdb.is_trusted_maintainer(m) {
+                MaintainerStatus::Trusted
+            } else {
+                MaintainerStatus::Untrusted
+            };
+            (m.clone(), status)
+        })
+        .collect::<Vec<_>>();
+    statuses.sort_by(|(a, _), (b, _)| a.cmp(b));
+    statuses
+}
+
+/// Check whether all `maintainers` of a package are trusted.
+///
+/// If any maintainer is distrusted, the package is untrusted, even if the maintainer is also
+/// trusted.  If all maintainers are trusted, the package is trusted.  Otherwise trust is
+/// indeterminate, but not untrusted: an unknown maintainer is not evidence against a package,
+/// and another check, e.g. a good signature, may still establish trust.  With
+/// [strict maintainers](TrustDatabase::strict_maintainers) the package is untrusted instead.
+///
+/// See [`classify_maintainers`] for the status of individual maintainers.
+pub fn check_maintainers(trustdb: &TrustDatabase, maintainers: &HashSet<String>) -> TrustVerdict {
+    if maintainers.is_empty() {
+        return TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::MaintainersUnknown);
+    }
+    let statuses = classify_maintainers(trustdb, maintainers);
+    let with_status = |wanted| {
+        statuses
+            .iter()
+            .filter(move |(_, status)| *status == wanted)
+            .map(|(m, _)| m.clone())
+    };
+    let distrusted = with_status(MaintainerStatus::Distrusted).collect::<Vec<_>>();
+    if !distrusted.is_empty() {
+        return distrusted.into_iter().fold(
+            TrustVerdict::new(Trust::Untrusted),
+            |verdict, maintainer| {
+                verdict.add_reason(TrustReason::MaintainerDistrusted { maintainer })
+            },
+        );
+    }
+    let untrusted = with_status(MaintainerStatus::Untrusted).collect::<Vec<_>>();
+    if untrusted.is_empty() {
+        TrustVerdict::new(Trust::Trusted).add_reason(TrustReason::AllMaintainersTrusted)
+    } else {
+        let trust = if trustdb.strict_maintainers {
+            Trust::Untrusted
+        } else {
+            Trust::Indeterminate
+        };
+        untrusted
+            .into_iter()
+            .fold(TrustVerdict::new(trust), |verdict, maintainer| {
+                verdict.add_reason(TrustReason::MaintainerNotTrusted { maintainer })
+            })
+    }
+}
+
+/// Check whether `package` is orphaned.
+///
+/// An orphaned package has no maintainer in the AUR, so anyone can adopt it and push changes,
+/// which gives an indeterminate verdict.  A package with a maintainer gets the
+/// [top verdict](TrustVerdict::top).
+///
+/// Unlike [`check_maintainers`] with no maintainers, which means that the maintainers are not
+/// known, an orphaned package is known to have no maintainer.
+pub fn check_orphaned(package: &AurPackage) -> TrustVerdict {
+    match package.maintainer {
+        None => TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::Orphaned),
+        Some(_) => TrustVerdict::top(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aur::mock::package_1password;
+    use pretty_assertions::assert_eq;
+
+    fn package(maintainer: Option<&str>) -> AurPackage {
+        let mut package = package_1password();
+        package.maintainer = maintainer.map(Into::into);
+        package
+    }
+
+    #[test]
+    fn orphaned_package() {
+        let verdict = check_orphaned(&package(None));
+        assert_eq!(
+            verdict,
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::Orphaned)
+        );
+        assert_eq!(verdict.reasons()[0].to_string(), "Package is orphaned");
+    }
+
+    #[test]
+    fn maintained_package() {
+        assert_eq!(
+            check_orphaned(&package(Some("1Password"))),
+            TrustVerdict::new(Trust::Trusted)
+        );
+    }
+
+    #[test]
+    fn classify_mixed_maintainers() {
+        let trustdb = TrustDatabase::new()
+            .trust_maintainer("alice".into())
+            .trust_maintainer("mallory".into())
+            .distrust_maintainer("mallory".into())
+            .distrust_maintainer("eve".into());
+        let maintainers =
+            HashSet::from(["mallory".into(), "bob".into(), "alice".into(), "eve".into()]);
+        assert_eq!(
+            classify_maintainers(&trustdb, &maintainers),
+            vec![
+                ("alice".into(), MaintainerStatus::Trusted),
+                ("bob".into(), MaintainerStatus::Untrusted),
+                ("eve".into(), MaintainerStatus::Distrusted),
+                ("mallory".into(), MaintainerStatus::Distrusted),
+            ]
+        );
+        assert!(classify_maintainers(&trustdb, &HashSet::new()).is_empty());
+    }
+
+    #[test]
+    fn no_maintainers() {
+        let verdict = check_maintainers(&TrustDatabase::new(), &HashSet::new());
+        assert_eq!(
+            verdict,
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::MaintainersUnknown)
+        );
+    }
+
+    #[test]
+    fn all_maintainers_trusted() {
+        let trustdb = TrustDatabase::new()
+            .trust_maintainer("foo".into())
+            .trust_maintainer("bar".into());
+        let maintainers = HashSet::from(["foo".into(), "bar".into()]);
+        assert_eq!(
+            check_maintainers(&trustdb, &maintainers).trust(),
+            Trust::Trusted
+        );
+    }
+
+    #[test]
+    fn some_maintainers_untrusted() {
+        let trustdb = TrustDatabase::new().trust_maintainer("foo".into());
+        let maintainers = HashSet::from(["foo".into(), "bar".into(), "baz".into()]);
+        assert_eq!(
+            check_maintainers(&trustdb, &maintainers),
+            TrustVerdict::new(Trust::Indeterminate)
+                .add_reason(TrustReason::MaintainerNotTrusted {
+                    maintainer: "bar".into(),
+                })
+                .add_reason(TrustReason::MaintainerNotTrusted {
+                    maintainer: "baz".into(),
+                })
+        );
+    }
+
+    #[test]
+    fn strict_maintainers_untrusted() {
+        let trustdb = TrustDatabase::new()
+            .trust_maintainer("foo".into())
+            .strict_maintainers(true);
+        let maintainers = HashSet::from(["foo".into(), "bar".into()]);
+        assert_eq!(
+            check_maintainers(&trustdb, &maintainers),
+            TrustVerdict::new(Trust::Untrusted).add_reason(TrustReason::MaintainerNotTrusted {
+                maintainer: "bar".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn strict_maintainers_trusted_or_unknown() {
+        let trustdb = TrustDatabase::new()
+            .trust_maintainer("foo".into())
+            .strict_maintainers(true);
+        assert_eq!(
+            check_maintainers(&trustdb, &HashSet::from(["foo".into()])).trust(),
+            Trust::Trusted
+        );
+        assert_eq!(
+            check_maintainers(&trustdb, &HashSet::new()).trust(),
+            Trust::Indeterminate
+        );
+    }
+
+    #[test]
+    fn trusted_and_distrusted_maintainer() {
+        let trustdb = TrustDatabase::new()
+            .trust_maintainer("foo".into())
+            .trust_maintainer("bar".into())
+            .distrust_maintainer("bar".into());
+        let maintainers = HashSet::from(["foo".into(), "bar".into()]);
+        assert_eq!(
+            check_maintainers(&trustdb, &maintainers),
+            TrustVerdict::new(Trust::Untrusted).add_reason(TrustReason::MaintainerDistrusted {
+                maintainer: "bar".into(),
+            })
+        );
+    }
+}
@@ -0,0 +1,64 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Lower trust in packages with few votes.
+
+use crate::aur::rpc::AurPackage;
+use crate::lattice::HasTop;
+
+use super::{Trust, TrustReason, TrustVerdict};
+
+/// Check whether `package` has at least `min_votes` votes.
+///
+/// A package with fewer votes gets an indeterminate verdict, and a package with enough votes gets
+/// the [top verdict](TrustVerdict::top).
+pub fn check_popularity(package: &AurPackage, min_votes: u32) -> TrustVerdict {
+    check_votes(package.num_votes, min_votes)
+}
+
+/// Check whether `votes` reach `min_votes`; see [`check_popularity`].
+pub(super) fn check_votes(votes: u32, min_votes: u32) -> TrustVerdict {
+    if votes < min_votes {
+        TrustVerdict::new(Trust::Indeterminate)
+            .add_reason(TrustReason::NotEnoughVotes { votes, min_votes })
+    } else {
+        TrustVerdict::top()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aur::mock::package_1password;
+    use pretty_assertions::assert_eq;
+
+    fn package(num_votes: u32) -> AurPackage {
+        let mut package = package_1password();
+        package.num_votes = num_votes;
+        package
+    }
+
+    #[test]
+    fn popular_package() {
+        assert_eq!(
+            check_popularity(&package(134), 10),
+            TrustVerdict::new(Trust::Trusted)
+        );
+        assert_eq!(
+            check_popularity(&package(10), 10),
+            TrustVerdict::new(Trust::Trusted)
+        );
+    }
+
+    #[test]
+    fn unpopular_package() {
+        assert_eq!(
+            check_popularity(&package(2), 10),
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::NotEnoughVotes {
+                votes: 2,
+                min_votes: 10
+            })
+        );
+    }
+}
@@ -0,0 +1,669 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Determine package trust by checking commit signatures.
+
+use std::collections::HashSet;
+
+use crate::lattice::MeetSemiLattice;
+
+use super::{
+    CommitSignature, GitCommit, SignatureValidity, Trust, TrustDatabase, TrustReason, TrustVerdict,
+};
+
+/// Check the signature of the given `commit`.
+///
+/// A good signature by a key or a signer trusted in `trustdb` makes the commit trusted, and a
+/// good signature by any other key gives no evidence either way, so its trust is indeterminate.
+/// Any other signature makes the commit untrusted.
+///
+/// An unsigned commit gives no evidence either way, so its trust is indeterminate.  Likewise for
+/// a signature of unknown validity, which mostly means that the signing key is not in the local
+/// keyring.
+pub fn check_commit_signature(trustdb: &TrustDatabase, commit: &GitCommit) -> TrustVerdict {
+    check_commit_signature_with(commit, |signature| is_trusted_signature(trustdb, signature))
+}
+
+/// Whether `trustdb` trusts the key or the signer of `signature`, regardless of its validity.
+pub(super) fn is_trusted_signature(trustdb: &TrustDatabase, signature: &CommitSignature) -> bool {
+    signature.keys().any(|key| trustdb.is_trusted_key(key))
+        || trustdb.is_trusted_signer(&signature.signer)
+}
+
+/// Check the signature of the given `commit` strictly.
+///
+/// Trust the commit only if it has a good signature and the signing key is trusted in
+/// `trustdb`; unlike [`check_commit_signature`] trusted signers do not count.  Distrust the
+/// commit otherwise, even if it is unsigned or the validity of its signature is unknown, and give
+/// a reason for every condition which does not hold.
+pub fn check_commit_signature_strict(trustdb: &TrustDatabase, commit: &GitCommit) -> TrustVerdict {
+    let validity = check_signature_validity(commit);
+    let signature = match &commit.signature {
+        Some(signature) => signature,
+        None => {
+            return TrustVerdict::new(Trust::Untrusted)
+                .add_reason(TrustReason::NoSignature {
+                    sha1: commit.abbrev_sha1.to_string(),
+                })
+                .add_reason(TrustReason::SignatureRequired)
+        }
+    };
+    let good = signature.validity == SignatureValidity::Good;
+    let trusted_key = signature.keys().any(|key| trustdb.is_trusted_key(key));
+    if good && trusted_key {
+        return validity;
+    }
+    let mut verdict = TrustVerdict::new(Trust::Untrusted);
+    if !good {
+        verdict = validity
+            .reasons()
+            .iter()
+            .cloned()
+            .fold(verdict, TrustVerdict::add_reason);
+    }
+    if !trusted_key {
+        verdict = verdict.add_reason(TrustReason::UntrustedKey {
+            sha1: commit.abbrev_sha1.to_string(),
+            signer: signature.signer.clone(),
+            key: signature.key().to_string(),
+        });
+    }
+    verdict
+}
+
+/// Check the signature of the given `commit` against a set of `trusted_keys`.
+///
+/// Like [`check_commit_signature`], but only trust a good signature by one of `trusted_keys`,
+/// regardless of any trust database, e.g. to accept only the keys of the maintainers of a
+/// package.  The keys are key IDs or full fingerprints.
+pub fn check_commit_signature_against(
+    commit: &GitCommit,
+    trusted_keys: &HashSet<String>,
+) -> TrustVerdict {
+    check_commit_signature_with(commit, |signature| {
+        signature.keys().any(|key| trusted_keys.contains(key))
+    })
+}
+
+/// Check the signature of the given `commit`, and trust a good signature if `is_trusted`.
+fn check_commit_signature_with<F>(commit: &GitCommit, is_trusted: F) -> TrustVerdict
+where
+    F: FnOnce(&CommitSignature) -> bool,
+{
+    match &commit.signature {
+        Some(signature)
+            if signature.validity == SignatureValidity::Good && !is_trusted(signature) =>
+        {
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::UntrustedKey {
+                sha1: commit.abbrev_sha1.to_string(),
+                signer: signature.signer.clone(),
+                key: signature.key().to_string(),
+            })
+        }
+        _ => check_signature_validity(commit),
+    }
+}
+
+/// Check the signatures of all given `commits`, e.g. all commits of a branch.
+///
+/// Check every commit with [`check_commit_signature`] and return the meet of all verdicts, so
+/// that a single commit with a bad signature anywhere makes the whole chain untrusted, and the
+/// chain is only trusted if all commits have good signatures by trusted keys.  The reasons name
+/// the offending commits.
+///
+/// An empty chain gives no evidence either way, so its trust is indeterminate.
+pub fn check_commit_chain(trustdb: &TrustDatabase, commits: &[GitCommit]) -> TrustVerdict {
+    commits
+        .iter()
+        .map(|commit| check_commit_signature(trustdb, commit))
+        .reduce(MeetSemiLattice::meet)
+        .unwrap_or_default()
+}
+
+/// Get the email address from the user ID of a `signer`.
+///
+/// Return the address in angle brackets of a `Name <email>` user ID, or the whole user ID if it
+/// is a bare email address.
+pub(super) fn signer_email(signer: &str) -> Option<&str> {
+    let signer = signer.trim();
+    let email = match signer.rfind('<') {
+        Some(start) => signer[start + 1..].strip_suffix('>')?,
+        None => signer,
+    };
+    let email = email.trim();
+    if email.contains('@') && !email.contains(char::is_whitespace) {
+        Some(email)
+    } else {
+        None
+    }
+}
+
+/// Whether `signer` matches the given signer `pattern`.
+///
+/// See [`TrustDatabase::trust_signer`] for the format of `pattern`.
+pub(super) fn signer_matches(pattern: &str, signer: &str) -> bool {
+    if pattern == signer {
+        return true;
+    }
+    match signer_email(signer) {
+        Some(email) => {
+            let email = email.to_lowercase();
+            let domain = email.rfind('@').map(|index| &email[index..]);
+            let pattern = pattern.to_lowercase();
+            pattern == email || Some(pattern.as_str()) == domain
+        }
+        None => false,
+    }
+}
+
+/// Check whether the signer of `signature` is one of the `maintainers` of a package.
+///
+/// Identify maintainers by the signers associated with them in `trustdb`; see
+/// [`TrustDatabase::maintainer_signer`].  If the signer is a maintainer, return a trusted verdict.
+/// Otherwise trust is indeterminate, because a maintainer may simply not have an associated
+/// signer; with [strict maintainers](TrustDatabase::strict_maintainers) the signature is
+/// untrusted instead.
+///
+/// This check only compares identities, regardless of the validity of `signature`; meet its
+/// verdict with the verdict of [`check_commit_signature`] to catch commits signed by trusted
+/// keys of people who do not maintain the package.
+pub fn check_signer_is_maintainer(
+    trustdb: &TrustDatabase,
+    signature: &CommitSignature,
+    maintainers: &HashSet<String>,
+) -> TrustVerdict {
+    let mut matching = maintainers
+        .iter()
+        .filter(|maintainer| trustdb.is_maintainer_signer(maintainer, &signature.signer))
+        .collect::<Vec<_>>();
+    matching.sort();
+    match matching.first() {
+        Some(maintainer) => {
+            TrustVerdict::new(Trust::Trusted).add_reason(TrustReason::SignerIsMaintainer {
+                signer: signature.signer.clone(),
+                maintainer: maintainer.to_string(),
+            })
+        }
+        None => {
+            let trust = if trustdb.strict_maintainers {
+                Trust::Untrusted
+            } else {
+                Trust::Indeterminate
+            };
+            TrustVerdict::new(trust).add_reason(TrustReason::SignerNotMaintainer {
+                signer: signature.signer.clone(),
+            })
+        }
+    }
+}
+
+/// The trust in a signature with the given `validity`, regardless of the signing key.
+///
+/// A good signature is trusted, and a signature of unknown validity gives no evidence either
+/// way.  Any other signature is untrusted.
+pub(super) fn validity_trust(validity: SignatureValidity) -> Trust {
+    match validity {
+        SignatureValidity::Good => Trust::Trusted,
+        SignatureValidity::UnknownValidity => Trust::Indeterminate,
+        SignatureValidity::BadSignature
+        | SignatureValidity::ExpiredSignature
+        | SignatureValidity::ExpiredKey
+        | SignatureValidity::RevokedKey => Trust::Untrusted,
+    }
+}
+
+/// Check the validity of the signature of the given `commit`, regardless of the signing key.
+///
+/// A good signature makes the commit trusted; see [`check_commit_signature`] otherwise.
+pub(super) fn check_signature_validity(commit: &GitCommit) -> TrustVerdict {
+    let sha1 = commit.abbrev_sha1.to_string();
+    match &commit.signature {
+        None => {
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::NoSignature { sha1 })
+        }
+        Some(signature) => {
+            let validity = signature.validity;
+            let signer = signature.signer.clone();
+            let key = signature.key().to_string();
+            let reason = match validity {
+                SignatureValidity::Good => TrustReason::GoodSignature { sha1, signer, key },
+                SignatureValidity::BadSignature => TrustReason::BadSignature { sha1, signer, key },
+                SignatureValidity::UnknownValidity => {
+                    TrustReason::UnknownValidity { sha1, signer, key }
+                }
+                SignatureValidity::ExpiredSignature => TrustReason::ExpiredSignature {
+                    sha1,
+                    signer,
+                    key,
+                    made: signature.sig_timestamp,
+                },
+                SignatureValidity::ExpiredKey => TrustReason::ExpiredKey {
+                    sha1,
+                    signer,
+                    key,
+                    expired: signature.key_expiry,
+                },
+                SignatureValidity::RevokedKey => TrustReason::RevokedKey { sha1, signer, key },
+            };
+            TrustVerdict::new(validity_trust(validity)).add_reason(reason)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quickcheck_macros::quickcheck;
+
+    fn commit(signature: Option<CommitSignature>) -> GitCommit {
+        GitCommit {
+            abbrev_sha1: "ae5a1b0".try_into().unwrap(),
+            signature,
+        }
+    }
+
+    fn signed_commit(sha1: &str, validity: SignatureValidity) -> GitCommit {
+        GitCommit {
+            abbrev_sha1: sha1.try_into().unwrap(),
+            signature: Some(CommitSignature {
+                validity,
+                signer: "Jane Doe <jane@example.com>".into(),
+                key_id: "SHA256:key".into(),
+                fingerprint: None,
+                algorithm: None,
+                key_expiry: None,
+                sig_timestamp: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn strict_good_signature_untrusted_key() {
+        let trustdb = TrustDatabase::new()
+            .trust_key("SHA256:other".into())
+            .trust_signer("jane@example.com".into());
+        let commit = signed_commit("ae5a1b0", SignatureValidity::Good);
+        assert_eq!(
+            check_commit_signature_strict(&trustdb, &commit),
+            TrustVerdict::new(Trust::Untrusted).add_reason(TrustReason::UntrustedKey {
+                sha1: "ae5a1b0".into(),
+                signer: "Jane Doe <jane@example.com>".into(),
+                key: "SHA256:key".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn strict_trusted_key_bad_signature() {
+        let trustdb = TrustDatabase::new().trust_key("SHA256:key".into());
+        let commit = signed_commit("ae5a1b0", SignatureValidity::BadSignature);
+        assert_eq!(
+            check_commit_signature_strict(&trustdb, &commit),
+            TrustVerdict::new(Trust::Untrusted).add_reason(TrustReason::BadSignature {
+                sha1: "ae5a1b0".into(),
+                signer: "Jane Doe <jane@example.com>".into(),
+                key: "SHA256:key".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn strict_good_signature_trusted_key() {
+        let trustdb = TrustDatabase::new().trust_key("SHA256:key".into());
+        let commit = signed_commit("ae5a1b0", SignatureValidity::Good);
+        assert_eq!(
+            check_commit_signature_strict(&trustdb, &commit),
+            check_commit_signature(&trustdb, &commit)
+        );
+        assert_eq!(
+            check_commit_signature_strict(&trustdb, &commit).trust(),
+            Trust::Trusted
+        );
+    }
+
+    #[test]
+    fn strict_unknown_validity_untrusted_key() {
+        let commit = signed_commit("ae5a1b0", SignatureValidity::UnknownValidity);
+        let verdict = check_commit_signature_strict(&TrustDatabase::new(), &commit);
+        assert_eq!(verdict.trust(), Trust::Untrusted);
+        assert_eq!(verdict.reasons().len(), 2);
+    }
+
+    #[test]
+    fn expired_key_reason_with_expiry() {
+        let mut commit = signed_commit("ae5a1b0", SignatureValidity::ExpiredKey);
+        commit.signature.as_mut().unwrap().key_expiry = Some(1669900000);
+        let verdict = check_signature_validity(&commit);
+        assert_eq!(
+            verdict,
+            TrustVerdict::new(Trust::Untrusted).add_reason(TrustReason::ExpiredKey {
+                sha1: "ae5a1b0".into(),
+                signer: "Jane Doe <jane@example.com>".into(),
+                key: "SHA256:key".into(),
+                expired: Some(1669900000),
+            })
+        );
+        assert_eq!(
+            verdict.reasons()[0].to_string(),
+            "Commit ae5a1b0 has signature from Jane Doe <jane@example.com> with expired key SHA256:key which expired on 2022-12-01 13:06:40 UTC"
+        );
+    }
+
+    #[test]
+    fn expired_signature_reason_with_timestamp() {
+        let mut commit = signed_commit("ae5a1b0", SignatureValidity::ExpiredSignature);
+        commit.signature.as_mut().unwrap().sig_timestamp = Some(951782400);
+        assert_eq!(
+            check_signature_validity(&commit).reasons()[0].to_string(),
+            "Commit ae5a1b0 has expired signature from Jane Doe <jane@example.com> with key SHA256:key made on 2000-02-29 00:00:00 UTC"
+        );
+    }
+
+    #[test]
+    fn signer_email_formats() {
+        assert_eq!(
+            signer_email("Jane Doe <jane@example.com>"),
+            Some("jane@example.com")
+        );
+        assert_eq!(
+            signer_email("Jane <Doe> <jane@example.com>"),
+            Some("jane@example.com")
+        );
+        assert_eq!(signer_email("jane@example.com"), Some("jane@example.com"));
+        assert_eq!(signer_email("Jane Doe"), None);
+        assert_eq!(signer_email("Jane Doe <jane@example.com"), None);
+        assert_eq!(signer_email("Jane Doe <>"), None);
+    }
+
+    fn jane_signature() -> CommitSignature {
+        CommitSignature {
+            validity: SignatureValidity::Good,
+            signer: "Jane Doe <jane@example.com>".into(),
+            key_id: "SHA256:key".into(),
+            fingerprint: None,
+            algorithm: None,
+            key_expiry: None,
+            sig_timestamp: None,
+        }
+    }
+
+    #[test]
+    fn signer_is_maintainer() {
+        let trustdb =
+            TrustDatabase::new().maintainer_signer("jane".into(), "jane@example.com".into());
+        let maintainers = HashSet::from(["john".into(), "jane".into()]);
+        assert_eq!(
+            check_signer_is_maintainer(&trustdb, &jane_signature(), &maintainers),
+            TrustVerdict::new(Trust::Trusted).add_reason(TrustReason::SignerIsMaintainer {
+                signer: "Jane Doe <jane@example.com>".into(),
+                maintainer: "jane".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn signer_is_not_maintainer() {
+        let trustdb = TrustDatabase::new()
+            .maintainer_signer("jane".into(), "jane@example.com".into())
+            .maintainer_signer("john".into(), "@example.org".into());
+        let maintainers = HashSet::from(["john".into()]);
+        let verdict = check_signer_is_maintainer(&trustdb, &jane_signature(), &maintainers);
+        assert_eq!(
+            verdict,
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::SignerNotMaintainer {
+                signer: "Jane Doe <jane@example.com>".into(),
+            })
+        );
+        let trustdb = trustdb.strict_maintainers(true);
+        assert_eq!(
+            check_signer_is_maintainer(&trustdb, &jane_signature(), &maintainers).trust(),
+            Trust::Untrusted
+        );
+    }
+
+    #[test]
+    fn check_commit_signature_trusted_signer() {
+        let commit = |signer: &str| GitCommit {
+            abbrev_sha1: "ae5a1b0".try_into().unwrap(),
+            signature: Some(CommitSignature {
+                validity: SignatureValidity::Good,
+                signer: signer.into(),
+                key_id: "SHA256:key".into(),
+                fingerprint: None,
+                algorithm: None,
+                key_expiry: None,
+                sig_timestamp: None,
+            }),
+        };
+        let cases = [
+            (
+                "Jane Doe <jane@example.com>",
+                "Jane Doe <jane@example.com>",
+                true,
+            ),
+            (
+                "Jane Doe <jane@example.com>",
+                "John Doe <jane@example.com>",
+                false,
+            ),
+            ("jane@example.com", "Jane Doe <Jane@Example.com>", true),
+            ("jane@example.com", "jane@example.com", true),
+            ("jane@example.com", "John Doe <john@example.com>", false),
+            ("@example.com", "John Doe <john@example.com>", true),
+            ("@example.com", "john@EXAMPLE.com", true),
+            ("@example.com", "John Doe <john@example.com.evil>", false),
+            ("@example.com", "John Doe <john@sub.example.com>", false),
+            ("@example.com", "John Doe example.com", false),
+        ];
+        for (pattern, signer, trusted) in cases {
+            let trustdb = TrustDatabase::new().trust_signer(pattern.into());
+            assert_eq!(
+                trustdb.is_trusted_signer(signer),
+                trusted,
+                "{} matching {}",
+                pattern,
+                signer
+            );
+            let expected = if trusted {
+                Trust::Trusted
+            } else {
+                Trust::Indeterminate
+            };
+            assert_eq!(
+                check_commit_signature(&trustdb, &commit(signer)).trust(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn check_commit_chain_all_good() {
+        let trustdb = TrustDatabase::new().trust_key("SHA256:key".into());
+        let commits = [
+            signed_commit("1111111", SignatureValidity::Good),
+            signed_commit("2222222", SignatureValidity::Good),
+            signed_commit("3333333", SignatureValidity::Good),
+        ];
+        let verdict = check_commit_chain(&trustdb, &commits);
+        assert_eq!(verdict.trust(), Trust::Trusted);
+        assert_eq!(verdict.reasons().len(), 3);
+    }
+
+    #[test]
+    fn check_commit_chain_bad_commit_in_the_middle() {
+        let trustdb = TrustDatabase::new().trust_key("SHA256:key".into());
+        let commits = [
+            signed_commit("1111111", SignatureValidity::Good),
+            signed_commit("2222222", SignatureValidity::BadSignature),
+            signed_commit("3333333", SignatureValidity::Good),
+        ];
+        assert_eq!(
+            check_commit_chain(&trustdb, &commits),
+            TrustVerdict::new(Trust::Untrusted).add_reason(TrustReason::BadSignature {
+                sha1: "2222222".into(),
+                signer: "Jane Doe <jane@example.com>".into(),
+                key: "SHA256:key".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn check_commit_chain_empty() {
+        assert_eq!(
+            check_commit_chain(&TrustDatabase::new(), &[]),
+            TrustVerdict::new(Trust::Indeterminate)
+        );
+    }
+
+    #[quickcheck]
+    fn check_commit_signature_trusted_only_good_with_trusted_key(
+        validity: SignatureValidity,
+        key_trusted: bool,
+    ) {
+        let trustdb = if key_trusted {
+            TrustDatabase::new().trust_key("SHA256:key".into())
+        } else {
+            TrustDatabase::new()
+        };
+        let commit = commit(Some(CommitSignature {
+            validity,
+            signer: "Jane Doe <jane@example.com>".into(),
+            key_id: "SHA256:key".into(),
+            fingerprint: None,
+            algorithm: None,
+            key_expiry: None,
+            sig_timestamp: None,
+        }));
+        let trust = check_commit_signature(&trustdb, &commit).trust();
+        assert_eq!(
+            trust == Trust::Trusted,
+            validity == SignatureValidity::Good && key_trusted
+        );
+    }
+
+    #[test]
+    fn check_commit_signature_good_untrusted_key() {
+        let commit = commit(Some(CommitSignature {
+            validity: SignatureValidity::Good,
+            signer: "Jane Doe <jane@example.com>".into(),
+            key_id: "SHA256:key".into(),
+            fingerprint: None,
+            algorithm: None,
+            key_expiry: None,
+            sig_timestamp: None,
+        }));
+        assert_eq!(
+            check_commit_signature(&TrustDatabase::new(), &commit),
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::UntrustedKey {
+                sha1: "ae5a1b0".into(),
+                signer: "Jane Doe <jane@example.com>".into(),
+                key: "SHA256:key".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn check_commit_signature_fingerprint() {
+        let fingerprint = "8B2E8B6F6AB0C6A0E0F61E6F6F6D3A6D2C9A1B2C";
+        let commit = commit(Some(CommitSignature {
+            validity: SignatureValidity::Good,
+            signer: "Jane Doe <jane@example.com>".into(),
+            key_id: "6F6D3A6D2C9A1B2C".into(),
+            fingerprint: Some(fingerprint.into()),
+            algorithm: Some("EdDSA".into()),
+            key_expiry: None,
+            sig_timestamp: None,
+        }));
+        assert_eq!(
+            check_commit_signature(&TrustDatabase::new(), &commit),
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::UntrustedKey {
+                sha1: "ae5a1b0".into(),
+                signer: "Jane Doe <jane@example.com>".into(),
+                key: fingerprint.into(),
+            })
+        );
+        for key in [fingerprint, "6F6D3A6D2C9A1B2C"] {
+            let trustdb = TrustDatabase::new().trust_key(key.into());
+            assert_eq!(
+                check_commit_signature(&trustdb, &commit).trust(),
+                Trust::Trusted
+            );
+        }
+    }
+
+    #[test]
+    fn check_commit_signature_against_keys() {
+        let commit = commit(Some(CommitSignature {
+            validity: SignatureValidity::Good,
+            signer: "Jane Doe <jane@example.com>".into(),
+            key_id: "SHA256:key".into(),
+            fingerprint: None,
+            algorithm: None,
+            key_expiry: None,
+            sig_timestamp: None,
+        }));
+        let keys = HashSet::from(["SHA256:other".into(), "SHA256:key".into()]);
+        assert_eq!(
+            check_commit_signature_against(&commit, &keys).trust(),
+            Trust::Trusted
+        );
+        let keys = HashSet::from(["SHA256:other".into()]);
+        assert_eq!(
+            check_commit_signature_against(&commit, &keys),
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::UntrustedKey {
+                sha1: "ae5a1b0".into(),
+                signer: "Jane Doe <jane@example.com>".into(),
+                key: "SHA256:key".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn check_commit_signature_against_bad_signature() {
+        let commit = commit(Some(CommitSignature {
+            validity: SignatureValidity::BadSignature,
+            signer: "Jane Doe <jane@example.com>".into(),
+            key_id: "SHA256:key".into(),
+            fingerprint: None,
+            algorithm: None,
+            key_expiry: None,
+            sig_timestamp: None,
+        }));
+        let keys = HashSet::from(["SHA256:key".into()]);
+        assert_eq!(
+            check_commit_signature_against(&commit, &keys).trust(),
+            Trust::Untrusted
+        );
+    }
+
+    #[test]
+    fn check_commit_signature_unsigned() {
+        assert_eq!(
+            check_commit_signature(&TrustDatabase::new(), &commit(None)),
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::NoSignature {
+                sha1: "ae5a1b0".into()
+            })
+        );
+    }
+
+    #[test]
+    fn check_commit_signature_unknown_validity() {
+        let commit = commit(Some(CommitSignature {
+            validity: SignatureValidity::UnknownValidity,
+            signer: "Jane Doe <jane@example.com>".into(),
+            key_id: "B8A5A4F6E1C1E7D6".into(),
+            fingerprint: None,
+            algorithm: None,
+            key_expiry: None,
+            sig_timestamp: None,
+        }));
+        let verdict = check_commit_signature(&TrustDatabase::new(), &commit);
+        assert_eq!(verdict.trust(), Trust::Indeterminate);
+        assert_eq!(
+            verdict.reasons()[0].to_string(),
+            "Commit ae5a1b0 has signature from Jane Doe <jane@example.com> with key B8A5A4F6E1C1E7D6 of unknown validity; import key B8A5A4F6E1C1E7D6 into your keyring if you trust it"
+        );
+    }
+}
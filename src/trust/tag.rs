@@ -0,0 +1,177 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Determine package trust by checking tag signatures.
+
+use crate::lattice::{JoinSemiLattice, MeetSemiLattice};
+
+use super::signature::{is_trusted_signature, validity_trust};
+use super::{
+    check_commit_signature, GitCommit, GitTag, SignatureValidity, Trust, TrustDatabase,
+    TrustReason, TrustVerdict,
+};
+
+/// Check the signature of the given `tag`.
+///
+/// Like [`check_commit_signature`]: a good signature by a key or a signer trusted in `trustdb`
+/// makes the tag trusted, and a good signature by any other key, an unknown validity, or a
+/// missing signature give no evidence either way.  Any other signature makes the tag untrusted.
+pub fn check_tag_signature(trustdb: &TrustDatabase, tag: &GitTag) -> TrustVerdict {
+    let name = tag.name.clone();
+    match &tag.signature {
+        None => TrustVerdict::new(Trust::Indeterminate)
+            .add_reason(TrustReason::TagNotSigned { tag: name }),
+        Some(signature)
+            if signature.validity == SignatureValidity::Good
+                && !is_trusted_signature(trustdb, signature) =>
+        {
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::UntrustedTagKey {
+                tag: name,
+                signer: signature.signer.clone(),
+                key: signature.key().to_string(),
+            })
+        }
+        Some(signature) => TrustVerdict::new(validity_trust(signature.validity)).add_reason(
+            TrustReason::TagSignature {
+                tag: name,
+                validity: signature.validity,
+                signer: signature.signer.clone(),
+                key: signature.key().to_string(),
+            },
+        ),
+    }
+}
+
+/// Check the signatures of the given `commit` and of a `tag` on this commit.
+///
+/// Trust the commit if either the commit or the tag has a good signature by a trusted key, i.e.
+/// take the join of both verdicts.  However, if either signature makes the commit untrusted, e.g.
+/// because it's a bad signature, the commit is untrusted regardless of the other signature.
+///
+/// Ignore `tag` if it does not point to `commit`, and only check the signature of the commit.
+pub fn check_commit_or_tag_signature(
+    trustdb: &TrustDatabase,
+    commit: &GitCommit,
+    tag: Option<&GitTag>,
+) -> TrustVerdict {
+    let commit_verdict = check_commit_signature(trustdb, commit);
+    match tag.filter(|tag| tag.points_to(commit)) {
+        None => commit_verdict,
+        Some(tag) => {
+            let tag_verdict = check_tag_signature(trustdb, tag);
+            if commit_verdict.trust() == Trust::Untrusted || tag_verdict.trust() == Trust::Untrusted
+            {
+                commit_verdict.meet(tag_verdict)
+            } else {
+                commit_verdict.join(tag_verdict)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::trust::TagSignature;
+    use pretty_assertions::assert_eq;
+
+    fn unsigned_commit() -> GitCommit {
+        GitCommit::new("ae5a1b0".try_into().unwrap(), None)
+    }
+
+    fn tag(target: &str, validity: SignatureValidity) -> GitTag {
+        GitTag::new(
+            "v1.0.0".into(),
+            target.try_into().unwrap(),
+            Some(TagSignature {
+                validity,
+                signer: "Jane Doe <jane@example.com>".into(),
+                key_id: "SHA256:key".into(),
+                fingerprint: None,
+                algorithm: None,
+                key_expiry: None,
+                sig_timestamp: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn tag_signature_trusted_key() {
+        let trustdb = TrustDatabase::new().trust_key("SHA256:key".into());
+        assert_eq!(
+            check_tag_signature(&trustdb, &tag("ae5a1b0", SignatureValidity::Good)),
+            TrustVerdict::new(Trust::Trusted).add_reason(TrustReason::TagSignature {
+                tag: "v1.0.0".into(),
+                validity: SignatureValidity::Good,
+                signer: "Jane Doe <jane@example.com>".into(),
+                key: "SHA256:key".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn tag_signature_untrusted_key() {
+        let verdict = check_tag_signature(
+            &TrustDatabase::new(),
+            &tag("ae5a1b0", SignatureValidity::Good),
+        );
+        assert_eq!(verdict.trust(), Trust::Indeterminate);
+        assert_eq!(
+            verdict.reasons()[0].to_string(),
+            "Tag v1.0.0 has good signature from Jane Doe <jane@example.com> with untrusted key SHA256:key"
+        );
+    }
+
+    #[test]
+    fn tag_not_signed() {
+        let tag = GitTag::new("v1.0.0".into(), "ae5a1b0".try_into().unwrap(), None);
+        assert_eq!(
+            check_tag_signature(&TrustDatabase::new(), &tag),
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::TagNotSigned {
+                tag: "v1.0.0".into()
+            })
+        );
+    }
+
+    #[test]
+    fn signed_tag_on_unsigned_commit() {
+        let trustdb = TrustDatabase::new().trust_key("SHA256:key".into());
+        let tag = tag("ae5a1b0d6f3c", SignatureValidity::Good);
+        let verdict = check_commit_or_tag_signature(&trustdb, &unsigned_commit(), Some(&tag));
+        assert_eq!(verdict.trust(), Trust::Trusted);
+        assert_eq!(
+            verdict.reasons(),
+            [TrustReason::TagSignature {
+                tag: "v1.0.0".into(),
+                validity: SignatureValidity::Good,
+                signer: "Jane Doe <jane@example.com>".into(),
+                key: "SHA256:key".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn tag_on_other_commit_ignored() {
+        let trustdb = TrustDatabase::new().trust_key("SHA256:key".into());
+        let tag = tag("1111111", SignatureValidity::Good);
+        assert_eq!(
+            check_commit_or_tag_signature(&trustdb, &unsigned_commit(), Some(&tag)),
+            check_commit_signature(&trustdb, &unsigned_commit())
+        );
+    }
+
+    #[test]
+    fn bad_tag_signature_distrusts_commit() {
+        let trustdb = TrustDatabase::new().trust_key("SHA256:key".into());
+        let commit = GitCommit::new(
+            "ae5a1b0".try_into().unwrap(),
+            tag("ae5a1b0", SignatureValidity::Good).signature,
+        );
+        let tag = tag("ae5a1b0", SignatureValidity::BadSignature);
+        assert_eq!(
+            check_commit_or_tag_signature(&trustdb, &commit, Some(&tag)).trust(),
+            Trust::Untrusted
+        );
+    }
+}
@@ -4,10 +4,33 @@
 
 //! Types and traits for representing and checking trust.
 
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::aur::rpc::AurPackage;
 use crate::lattice::{HasBottom, HasTop, JoinSemiLattice, MeetSemiLattice};
 
 /// Trust in an AUR package.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+///
+/// With the `quickcheck` feature enabled [`Trust`] implements `quickcheck::Arbitrary`:
+///
+/// ```
+/// # #[cfg(feature = "quickcheck")]
+/// # {
+/// use aur_trust::trust::Trust;
+/// use quickcheck::{Arbitrary, Gen};
+///
+/// let trust = Trust::arbitrary(&mut Gen::new(10));
+/// assert!(Trust::Untrusted <= trust && trust <= Trust::Trusted);
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Trust {
     /// The package is not trusted.
     Untrusted = 0,
@@ -17,6 +40,19 @@ pub enum Trust {
     Trusted = 2,
 }
 
+impl Trust {
+    /// The exit code of a process which checked trust in packages, for this trust.
+    ///
+    /// Return 0 for trusted packages, 1 for indeterminate trust, and 2 for untrusted packages.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Trust::Trusted => 0,
+            Trust::Indeterminate => 1,
+            Trust::Untrusted => 2,
+        }
+    }
+}
+
 impl HasTop for Trust {
     /// [`Trust::Trusted`], as the top element of the [`Trust`] enum.
     fn top() -> Self {
@@ -38,6 +74,16 @@ impl Default for Trust {
     }
 }
 
+impl fmt::Display for Trust {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Trust::Trusted => "trusted",
+            Trust::Indeterminate => "indeterminate",
+            Trust::Untrusted => "untrusted",
+        })
+    }
+}
+
 impl JoinSemiLattice for Trust {
     fn join(self, other: Self) -> Self {
         self.max(other)
@@ -50,12 +96,1115 @@ impl MeetSemiLattice for Trust {
     }
 }
 
+#[cfg(any(test, feature = "quickcheck"))]
+impl quickcheck::Arbitrary for Trust {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        g.choose(&[Trust::Trusted, Trust::Indeterminate, Trust::Untrusted])
+            .unwrap()
+            .to_owned()
+    }
+}
+
+/// A reason for a trust verdict.
+///
+/// The [`Display`](fmt::Display) implementation describes the reason in human-readable form.
+///
+/// Reasons serialize with the snake case name of the reason as `kind`, and the fields of the
+/// reason, if any, as `details`, so that they deserialize to the same reason again.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "details", rename_all = "snake_case")]
+pub enum TrustReason {
+    /// A commit is not signed.
+    NoSignature {
+        /// The abbreviated SHA1 of the commit.
+        sha1: String,
+    },
+    /// A commit has a good signature by a trusted key.
+    GoodSignature {
+        /// The abbreviated SHA1 of the commit.
+        sha1: String,
+        /// The signer of the commit.
+        signer: String,
+        /// The signing key.
+        key: String,
+    },
+    /// A commit has a good signature by a key which is not trusted.
+    UntrustedKey {
+        /// The abbreviated SHA1 of the commit.
+        sha1: String,
+        /// The signer of the commit.
+        signer: String,
+        /// The signing key.
+        key: String,
+    },
+    /// A commit has a bad signature.
+    BadSignature {
+        /// The abbreviated SHA1 of the commit.
+        sha1: String,
+        /// The signer of the commit.
+        signer: String,
+        /// The signing key.
+        key: String,
+    },
+    /// A commit has a signature of unknown validity.
+    UnknownValidity {
+        /// The abbreviated SHA1 of the commit.
+        sha1: String,
+        /// The signer of the commit.
+        signer: String,
+        /// The signing key.
+        key: String,
+    },
+    /// A commit has an expired signature.
+    ExpiredSignature {
+        /// The abbreviated SHA1 of the commit.
+        sha1: String,
+        /// The signer of the commit.
+        signer: String,
+        /// The signing key.
+        key: String,
+        /// When the signature was made, as unix timestamp, if known.
+        made: Option<i64>,
+    },
+    /// A commit has a signature by an expired key.
+    ExpiredKey {
+        /// The abbreviated SHA1 of the commit.
+        sha1: String,
+        /// The signer of the commit.
+        signer: String,
+        /// The signing key.
+        key: String,
+        /// When the key expired, as unix timestamp, if known.
+        expired: Option<i64>,
+    },
+    /// A commit has a signature by a revoked key.
+    RevokedKey {
+        /// The abbreviated SHA1 of the commit.
+        sha1: String,
+        /// The signer of the commit.
+        signer: String,
+        /// The signing key.
+        key: String,
+    },
+    /// A good signature is required, but missing.
+    SignatureRequired,
+    /// A key was trusted on first use for a package.
+    KeyTrustedOnFirstUse {
+        /// The name of the package.
+        package: String,
+        /// The key trusted on first use.
+        key: String,
+    },
+    /// The signing key of a package changed since first use.
+    KeyChangedSinceFirstUse {
+        /// The abbreviated SHA1 of the commit.
+        sha1: String,
+        /// The name of the package.
+        package: String,
+        /// The signing key of the commit.
+        key: String,
+        /// The key first used for the package.
+        first_key: String,
+    },
+    /// The maintainers of a package are not known.
+    MaintainersUnknown,
+    /// All maintainers of a package are trusted.
+    AllMaintainersTrusted,
+    /// A maintainer is not trusted.
+    MaintainerNotTrusted {
+        /// The name of the maintainer.
+        maintainer: String,
+    },
+    /// A maintainer is distrusted.
+    MaintainerDistrusted {
+        /// The name of the maintainer.
+        maintainer: String,
+    },
+    /// A co-maintainer was ignored.
+    CoMaintainerIgnored {
+        /// The name of the co-maintainer.
+        maintainer: String,
+    },
+    /// A package has fewer votes than required.
+    NotEnoughVotes {
+        /// The number of votes of the package.
+        votes: u32,
+        /// The required number of votes.
+        min_votes: u32,
+    },
+    /// A package was submitted to the AUR only recently.
+    RecentlySubmitted {
+        /// How long ago the package was first submitted.
+        age: Duration,
+        /// The required age of packages.
+        min_age: Duration,
+    },
+    /// A package is orphaned, i.e. it has no maintainer.
+    Orphaned,
+    /// The signer of a commit is a maintainer of the package.
+    SignerIsMaintainer {
+        /// The signer.
+        signer: String,
+        /// The maintainer identified by the signer.
+        maintainer: String,
+    },
+    /// The signer of a commit is not a maintainer of the package.
+    SignerNotMaintainer {
+        /// The signer.
+        signer: String,
+    },
+    /// A tag is not signed.
+    TagNotSigned {
+        /// The name of the tag.
+        tag: String,
+    },
+    /// A tag has a signature.
+    TagSignature {
+        /// The name of the tag.
+        tag: String,
+        /// The validity of the signature.
+        validity: SignatureValidity,
+        /// The signer of the tag.
+        signer: String,
+        /// The signing key.
+        key: String,
+    },
+    /// A tag has a good signature by an untrusted key.
+    UntrustedTagKey {
+        /// The name of the tag.
+        tag: String,
+        /// The signer of the tag.
+        signer: String,
+        /// The signing key.
+        key: String,
+    },
+    /// Verification did not finish in time.
+    TimedOut,
+    /// Any other reason, in human-readable form.
+    Other(String),
+}
+
+impl fmt::Display for TrustReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrustReason::NoSignature { sha1 } => write!(f, "Commit {} is not signed", sha1),
+            TrustReason::GoodSignature { sha1, signer, key } => write!(
+                f,
+                "Commit {} has good signature from {} with key {}",
+                sha1, signer, key
+            ),
+            TrustReason::UntrustedKey { sha1, signer, key } => write!(
+                f,
+                "Commit {} has good signature from {} with untrusted key {}",
+                sha1, signer, key
+            ),
+            TrustReason::BadSignature { sha1, signer, key } => write!(
+                f,
+                "Commit {} has bad signature from {} with key {}",
+                sha1, signer, key
+            ),
+            TrustReason::UnknownValidity { sha1, signer, key } => write!(
+                f,
+                "Commit {} has signature from {} with key {} of unknown validity; import key {} into your keyring if you trust it",
+                sha1, signer, key, key
+            ),
+            TrustReason::ExpiredSignature {
+                sha1,
+                signer,
+                key,
+                made,
+            } => {
+                write!(
+                    f,
+                    "Commit {} has expired signature from {} with key {}",
+                    sha1, signer, key
+                )?;
+                match made {
+                    Some(made) => write!(f, " made on {}", format_timestamp(*made)),
+                    None => Ok(()),
+                }
+            }
+            TrustReason::ExpiredKey {
+                sha1,
+                signer,
+                key,
+                expired,
+            } => {
+                write!(
+                    f,
+                    "Commit {} has signature from {} with expired key {}",
+                    sha1, signer, key
+                )?;
+                match expired {
+                    Some(expired) => write!(f, " which expired on {}", format_timestamp(*expired)),
+                    None => Ok(()),
+                }
+            }
+            TrustReason::RevokedKey { sha1, signer, key } => write!(
+                f,
+                "Commit {} has signature from {} with revoked key {}",
+                sha1, signer, key
+            ),
+            TrustReason::SignatureRequired => write!(f, "Good signature required"),
+            TrustReason::KeyTrustedOnFirstUse { package, key } => {
+                write!(f, "Key {} trusted on first use for {}", key, package)
+            }
+            TrustReason::KeyChangedSinceFirstUse {
+                sha1,
+                package,
+                key,
+                first_key,
+            } => write!(
+                f,
+                "Commit {} signed with key {} but key changed since first use of {}, from {}",
+                sha1, key, package, first_key
+            ),
+            TrustReason::MaintainersUnknown => write!(f, "Maintainers unknown"),
+            TrustReason::AllMaintainersTrusted => write!(f, "All maintainers trusted"),
+            TrustReason::MaintainerNotTrusted { maintainer } => {
+                write!(f, "Maintainer {} is not trusted", maintainer)
+            }
+            TrustReason::MaintainerDistrusted { maintainer } => {
+                write!(f, "Maintainer {} is distrusted", maintainer)
+            }
+            TrustReason::CoMaintainerIgnored { maintainer } => {
+                write!(f, "Co-maintainer {} ignored", maintainer)
+            }
+            TrustReason::NotEnoughVotes { votes, min_votes } => write!(
+                f,
+                "Package has only {} votes, fewer than {}",
+                votes, min_votes
+            ),
+            TrustReason::RecentlySubmitted { age, min_age } => write!(
+                f,
+                "Package was first submitted only {} ago, less than {} ago",
+                format_age(*age),
+                format_age(*min_age)
+            ),
+            TrustReason::Orphaned => write!(f, "Package is orphaned"),
+            TrustReason::SignerIsMaintainer { signer, maintainer } => {
+                write!(f, "Signer {} is maintainer {}", signer, maintainer)
+            }
+            TrustReason::SignerNotMaintainer { signer } => {
+                write!(f, "Signer {} is not a maintainer of the package", signer)
+            }
+            TrustReason::TagNotSigned { tag } => write!(f, "Tag {} is not signed", tag),
+            TrustReason::TagSignature {
+                tag,
+                validity,
+                signer,
+                key,
+            } => {
+                let signature = match validity {
+                    SignatureValidity::Good => "good signature",
+                    SignatureValidity::BadSignature => "bad signature",
+                    SignatureValidity::UnknownValidity => "signature of unknown validity",
+                    SignatureValidity::ExpiredSignature => "expired signature",
+                    SignatureValidity::ExpiredKey => "signature with expired key",
+                    SignatureValidity::RevokedKey => "signature with revoked key",
+                };
+                write!(
+                    f,
+                    "Tag {} has {} from {} with key {}",
+                    tag, signature, signer, key
+                )
+            }
+            TrustReason::UntrustedTagKey { tag, signer, key } => write!(
+                f,
+                "Tag {} has good signature from {} with untrusted key {}",
+                tag, signer, key
+            ),
+            TrustReason::TimedOut => write!(f, "Verification timed out"),
+            TrustReason::Other(reason) => f.write_str(reason),
+        }
+    }
+}
+
+/// Format the unix `timestamp` as UTC date and time, e.g. `2022-11-30 09:20:00 UTC`.
+fn format_timestamp(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(86400);
+    let seconds = timestamp.rem_euclid(86400);
+    // Convert days since the epoch to a civil date, after Howard Hinnant's days_from_civil
+    // inverse, see <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// Format `age` in days, or in hours if less than a day.
+fn format_age(age: Duration) -> String {
+    let hours = age.as_secs() / 3600;
+    let (count, unit) = if hours < 24 {
+        (hours, "hour")
+    } else {
+        (hours / 24, "day")
+    };
+    if count == 1 {
+        format!("{} {}", count, unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+#[cfg(any(test, feature = "quickcheck"))]
+impl quickcheck::Arbitrary for TrustReason {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        match usize::arbitrary(g) % 26 {
+            0 => TrustReason::NoSignature {
+                sha1: String::arbitrary(g),
+            },
+            1 => TrustReason::GoodSignature {
+                sha1: String::arbitrary(g),
+                signer: String::arbitrary(g),
+                key: String::arbitrary(g),
+            },
+            2 => TrustReason::UntrustedKey {
+                sha1: String::arbitrary(g),
+                signer: String::arbitrary(g),
+                key: String::arbitrary(g),
+            },
+            3 => TrustReason::BadSignature {
+                sha1: String::arbitrary(g),
+                signer: String::arbitrary(g),
+                key: String::arbitrary(g),
+            },
+            4 => TrustReason::UnknownValidity {
+                sha1: String::arbitrary(g),
+                signer: String::arbitrary(g),
+                key: String::arbitrary(g),
+            },
+            5 => TrustReason::ExpiredSignature {
+                sha1: String::arbitrary(g),
+                signer: String::arbitrary(g),
+                key: String::arbitrary(g),
+                made: Option::arbitrary(g),
+            },
+            6 => TrustReason::ExpiredKey {
+                sha1: String::arbitrary(g),
+                signer: String::arbitrary(g),
+                key: String::arbitrary(g),
+                expired: Option::arbitrary(g),
+            },
+            7 => TrustReason::RevokedKey {
+                sha1: String::arbitrary(g),
+                signer: String::arbitrary(g),
+                key: String::arbitrary(g),
+            },
+            8 => TrustReason::SignatureRequired,
+            9 => TrustReason::KeyTrustedOnFirstUse {
+                package: String::arbitrary(g),
+                key: String::arbitrary(g),
+            },
+            10 => TrustReason::KeyChangedSinceFirstUse {
+                sha1: String::arbitrary(g),
+                package: String::arbitrary(g),
+                key: String::arbitrary(g),
+                first_key: String::arbitrary(g),
+            },
+            11 => TrustReason::MaintainersUnknown,
+            12 => TrustReason::AllMaintainersTrusted,
+            13 => TrustReason::MaintainerNotTrusted {
+                maintainer: String::arbitrary(g),
+            },
+            14 => TrustReason::MaintainerDistrusted {
+                maintainer: String::arbitrary(g),
+            },
+            15 => TrustReason::CoMaintainerIgnored {
+                maintainer: String::arbitrary(g),
+            },
+            16 => TrustReason::NotEnoughVotes {
+                votes: u32::arbitrary(g),
+                min_votes: u32::arbitrary(g),
+            },
+            17 => TrustReason::RecentlySubmitted {
+                age: Duration::arbitrary(g),
+                min_age: Duration::arbitrary(g),
+            },
+            18 => TrustReason::Orphaned,
+            19 => TrustReason::SignerIsMaintainer {
+                signer: String::arbitrary(g),
+                maintainer: String::arbitrary(g),
+            },
+            20 => TrustReason::SignerNotMaintainer {
+                signer: String::arbitrary(g),
+            },
+            21 => TrustReason::TagNotSigned {
+                tag: String::arbitrary(g),
+            },
+            22 => TrustReason::TagSignature {
+                tag: String::arbitrary(g),
+                validity: SignatureValidity::arbitrary(g),
+                signer: String::arbitrary(g),
+                key: String::arbitrary(g),
+            },
+            23 => TrustReason::UntrustedTagKey {
+                tag: String::arbitrary(g),
+                signer: String::arbitrary(g),
+                key: String::arbitrary(g),
+            },
+            24 => TrustReason::TimedOut,
+            _ => TrustReason::Other(String::arbitrary(g)),
+        }
+    }
+}
+
+/// A verdict about trust in a package, with reasons for the verdict.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TrustVerdict {
+    trust: Trust,
+    reasons: Vec<TrustReason>,
+}
+
+impl TrustVerdict {
+    /// Create a new verdict with the given `trust` and no reasons.
+    pub fn new(trust: Trust) -> Self {
+        Self {
+            trust,
+            reasons: Vec::new(),
+        }
+    }
+
+    /// The trust of this verdict.
+    pub fn trust(&self) -> Trust {
+        self.trust
+    }
+
+    /// The reasons for this verdict.
+    pub fn reasons(&self) -> &[TrustReason] {
+        &self.reasons
+    }
+
+    /// The reasons for this verdict which match `pred`.
+    pub fn reasons_matching<F: Fn(&TrustReason) -> bool>(&self, pred: F) -> Vec<&TrustReason> {
+        self.reasons.iter().filter(|reason| pred(reason)).collect()
+    }
+
+    /// The reasons for this verdict if it's untrusted.
+    ///
+    /// Return no reasons for trusted and indeterminate verdicts.
+    pub fn untrusted_reasons(&self) -> &[TrustReason] {
+        match self.trust {
+            Trust::Untrusted => &self.reasons,
+            Trust::Indeterminate | Trust::Trusted => &[],
+        }
+    }
+
+    /// A score for this verdict between 0 and 100, for sorting and display.
+    ///
+    /// An untrusted verdict scores 0, an indeterminate verdict 50 and a trusted verdict 100.
+    /// If the reasons of this verdict show that a package lacks votes, lower the score of an
+    /// indeterminate or trusted verdict by up to 25 points, in proportion to the missing votes.
+    /// The score thus never crosses into the range of a different trust, i.e. a verdict with
+    /// higher trust always has a higher score.
+    pub fn score(&self) -> u8 {
+        let (base, max_penalty) = match self.trust {
+            Trust::Untrusted => (0, 0),
+            Trust::Indeterminate => (50, 25),
+            Trust::Trusted => (100, 25),
+        };
+        let penalty = self
+            .reasons
+            .iter()
+            .filter_map(|reason| match reason {
+                TrustReason::NotEnoughVotes { votes, min_votes } if votes < min_votes => {
+                    let missing = u64::from(min_votes - votes);
+                    Some(max_penalty * missing / u64::from(*min_votes))
+                }
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+        // The penalty never exceeds max_penalty, so this cannot truncate
+        (base - penalty) as u8
+    }
+
+    /// Add a `reason` to this verdict.
+    pub fn add_reason(mut self, reason: TrustReason) -> Self {
+        self.push_reason(reason);
+        self
+    }
+
+    /// Add a `reason` to this verdict in place.
+    ///
+    /// See [`TrustVerdict::add_reason`].
+    pub fn push_reason(&mut self, reason: TrustReason) {
+        self.reasons.push(reason);
+    }
+
+    /// Change the trust of this verdict to `trust`.
+    pub fn set_trust(mut self, trust: Trust) -> Self {
+        self.set_trust_mut(trust);
+        self
+    }
+
+    /// Change the trust of this verdict to `trust` in place.
+    ///
+    /// See [`TrustVerdict::set_trust`].
+    pub fn set_trust_mut(&mut self, trust: Trust) {
+        self.trust = trust;
+    }
+
+    /// Apply `f` to every reason of this verdict, keeping trust and order of reasons.
+    pub fn map_reasons<F: FnMut(TrustReason) -> TrustReason>(self, f: F) -> Self {
+        Self {
+            trust: self.trust,
+            reasons: self.reasons.into_iter().map(f).collect(),
+        }
+    }
+
+    /// Merge the reasons of `self` and `other`, and sort them for a stable order.
+    ///
+    /// Drop duplicate reasons.  If either verdict has no reasons, keep the reasons of the other
+    /// verdict as they are.
+    fn merge_reasons(self, other: Self) -> Self {
+        if self.reasons.is_empty() {
+            return Self {
+                trust: self.trust,
+                reasons: other.reasons,
+            };
+        }
+        if other.reasons.is_empty() {
+            return self;
+        }
+        let mut reasons = self.reasons;
+        reasons.extend(other.reasons);
+        reasons.sort();
+        reasons.dedup();
+        Self {
+            trust: self.trust,
+            reasons,
+        }
+    }
+}
+
+impl fmt::Display for TrustVerdict {
+    /// Display the trust, followed by an indented list of reasons, one per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.trust)?;
+        for reason in &self.reasons {
+            write!(f, "\n  - {}", reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for TrustVerdict {
+    /// Order verdicts by trust first, so that less trusted verdicts come first.
+    ///
+    /// This order matches the lattice order on [`Trust`]: the [`MeetSemiLattice::meet`] of two
+    /// verdicts always has the trust of the lesser verdict.  Order verdicts of equal trust by
+    /// their sorted reasons, for a deterministic order, and finally by the order of their reasons,
+    /// to stay consistent with [`Eq`].
+    fn cmp(&self, other: &Self) -> Ordering {
+        fn sorted(reasons: &[TrustReason]) -> Vec<&TrustReason> {
+            let mut reasons = reasons.iter().collect::<Vec<_>>();
+            reasons.sort();
+            reasons
+        }
+        self.trust
+            .cmp(&other.trust)
+            .then_with(|| sorted(&self.reasons).cmp(&sorted(&other.reasons)))
+            .then_with(|| self.reasons.cmp(&other.reasons))
+    }
+}
+
+impl PartialOrd for TrustVerdict {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl JoinSemiLattice for TrustVerdict {
+    /// The verdict with more trust.
+    ///
+    /// If both verdicts have the same trust, merge their reasons.
+    fn join(self, other: Self) -> Self {
+        match self.trust.cmp(&other.trust) {
+            Ordering::Less => other,
+            Ordering::Greater => self,
+            Ordering::Equal => self.merge_reasons(other),
+        }
+    }
+}
+
+impl HasTop for TrustVerdict {
+    /// A trusted verdict without reasons.
+    ///
+    /// This verdict is the identity of [`MeetSemiLattice::meet`]: The meet of any verdict with
+    /// the top verdict is the verdict itself.
+    ///
+    /// Checks whose evidence alone never establishes trust, e.g. the number of votes of a
+    /// package, return this verdict if they find no problem.  Meet their verdicts with other
+    /// verdicts; they can only lower trust, never raise it.
+    fn top() -> Self {
+        Self::new(Trust::top())
+    }
+}
+
+impl MeetSemiLattice for TrustVerdict {
+    /// The verdict with less trust.
+    ///
+    /// If both verdicts have the same trust, merge their reasons.
+    fn meet(self, other: Self) -> Self {
+        match self.trust.cmp(&other.trust) {
+            Ordering::Less => self,
+            Ordering::Greater => other,
+            Ordering::Equal => self.merge_reasons(other),
+        }
+    }
+}
+
+#[cfg(any(test, feature = "quickcheck"))]
+impl quickcheck::Arbitrary for TrustVerdict {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Self {
+            trust: quickcheck::Arbitrary::arbitrary(g),
+            reasons: quickcheck::Arbitrary::arbitrary(g),
+        }
+    }
+}
+
+/// A report about trust in a package, with the verdicts of the individual checks.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TrustReport {
+    package: String,
+    verdict: TrustVerdict,
+    commit_verdict: TrustVerdict,
+    maintainer_verdict: TrustVerdict,
+}
+
+impl TrustReport {
+    /// Create a report for `package` with the combined `verdict` and the verdicts of the checks.
+    pub fn new(
+        package: String,
+        verdict: TrustVerdict,
+        commit_verdict: TrustVerdict,
+        maintainer_verdict: TrustVerdict,
+    ) -> Self {
+        Self {
+            package,
+            verdict,
+            commit_verdict,
+            maintainer_verdict,
+        }
+    }
+
+    /// The name of the package.
+    pub fn package(&self) -> &str {
+        &self.package
+    }
+
+    /// The combined verdict about the package.
+    pub fn verdict(&self) -> &TrustVerdict {
+        &self.verdict
+    }
+
+    /// The verdict about the signature of the HEAD commit of the package.
+    pub fn commit_verdict(&self) -> &TrustVerdict {
+        &self.commit_verdict
+    }
+
+    /// The verdict about the maintainers of the package.
+    pub fn maintainer_verdict(&self) -> &TrustVerdict {
+        &self.maintainer_verdict
+    }
+}
+
+/// The status of an individual maintainer in a trust database.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MaintainerStatus {
+    /// The maintainer is trusted.
+    Trusted,
+    /// The maintainer is neither trusted nor distrusted.
+    Untrusted,
+    /// The maintainer is distrusted, regardless of whether the maintainer is also trusted.
+    Distrusted,
+}
+
+/// The number of trusted, indeterminate and untrusted verdicts among many verdicts.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TrustSummary {
+    /// The number of trusted verdicts.
+    pub trusted: usize,
+    /// The number of indeterminate verdicts.
+    pub indeterminate: usize,
+    /// The number of untrusted verdicts.
+    pub untrusted: usize,
+}
+
+impl TrustSummary {
+    /// Count a verdict with the given `trust`.
+    pub fn count(mut self, trust: Trust) -> Self {
+        match trust {
+            Trust::Trusted => self.trusted += 1,
+            Trust::Indeterminate => self.indeterminate += 1,
+            Trust::Untrusted => self.untrusted += 1,
+        }
+        self
+    }
+}
+
+impl fmt::Display for TrustSummary {
+    /// Display the counts on a single line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} trusted, {} indeterminate, {} untrusted",
+            self.trusted, self.indeterminate, self.untrusted
+        )
+    }
+}
+
+/// The validity of a commit signature, as reported by git.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum SignatureValidity {
+    /// A good signature.
+    Good,
+    /// A bad signature.
+    BadSignature,
+    /// A good signature by a key whose validity is unknown.
+    UnknownValidity,
+    /// A good signature which has expired.
+    ExpiredSignature,
+    /// A good signature made by a key which has expired.
+    ExpiredKey,
+    /// A good signature made by a key which was revoked.
+    RevokedKey,
+}
+
+/// How severe a signature problem is.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// No problem.
+    Ok,
+    /// A problem which may be benign, e.g. an expired key.
+    Warning,
+    /// A problem which indicates tampering, e.g. a bad signature or a revoked key.
+    Critical,
+}
+
+impl SignatureValidity {
+    /// How severe a signature with this validity is.
+    pub fn severity(&self) -> Severity {
+        match self {
+            SignatureValidity::Good => Severity::Ok,
+            SignatureValidity::UnknownValidity
+            | SignatureValidity::ExpiredSignature
+            | SignatureValidity::ExpiredKey => Severity::Warning,
+            SignatureValidity::BadSignature | SignatureValidity::RevokedKey => Severity::Critical,
+        }
+    }
+}
+
+/// A warning about a likely mistake in a trust database.
+///
+/// See [`TrustDatabase::validate`](super::TrustDatabase::validate).
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ValidationWarning {
+    /// A trusted key is neither an SSH key fingerprint nor a GPG key ID or fingerprint.
+    ///
+    /// Such a key most likely never matches the key of any signature.
+    MalformedKey(String),
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationWarning::MalformedKey(key) => write!(
+                f,
+                "Trusted key {} is neither a SHA256 SSH key fingerprint, nor a GPG key ID or fingerprint",
+                key
+            ),
+        }
+    }
+}
+
+#[cfg(any(test, feature = "quickcheck"))]
+impl quickcheck::Arbitrary for SignatureValidity {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        g.choose(&[
+            SignatureValidity::Good,
+            SignatureValidity::BadSignature,
+            SignatureValidity::UnknownValidity,
+            SignatureValidity::ExpiredSignature,
+            SignatureValidity::ExpiredKey,
+            SignatureValidity::RevokedKey,
+        ])
+        .unwrap()
+        .to_owned()
+    }
+}
+
+/// The signature of a git commit.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CommitSignature {
+    /// The validity of the signature.
+    pub validity: SignatureValidity,
+    /// The signer, as user ID of the signing key.
+    pub signer: String,
+    /// The ID of the signing key, e.g. the long key ID of a GPG key.
+    #[serde(alias = "key")]
+    pub key_id: String,
+    /// The full fingerprint of the primary key of the signing key, if known.
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    /// The public key algorithm of the signing key, if known.
+    #[serde(default)]
+    pub algorithm: Option<String>,
+    /// When the signing key expired or expires, as unix timestamp, if known.
+    #[serde(default)]
+    pub key_expiry: Option<i64>,
+    /// When the signature was made, as unix timestamp, if known.
+    #[serde(default)]
+    pub sig_timestamp: Option<i64>,
+}
+
+impl CommitSignature {
+    /// The signing key, for display.
+    ///
+    /// Prefer the full fingerprint if known, and fall back to the key ID otherwise.
+    pub fn key(&self) -> &str {
+        self.fingerprint.as_deref().unwrap_or(&self.key_id)
+    }
+
+    /// All forms of the signing key, i.e. the fingerprint if known, and the key ID.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.fingerprint
+            .as_deref()
+            .into_iter()
+            .chain(std::iter::once(self.key_id.as_str()))
+    }
+}
+
+/// An abbreviated SHA1 of a git commit was invalid.
+#[derive(Error, Debug, Clone, Eq, PartialEq)]
+pub enum Sha1AbbrevError {
+    /// The abbreviated SHA1 was shorter than 7 or longer than 40 characters.
+    #[error("abbreviated SHA1 {0} must have between 7 and 40 characters")]
+    Length(String),
+    /// The abbreviated SHA1 had characters which are not hexadecimal digits.
+    #[error("abbreviated SHA1 {0} must only contain hexadecimal digits")]
+    NotHex(String),
+}
+
+/// An abbreviated SHA1 of a git commit.
+///
+/// A string of 7 to 40 hexadecimal digits.  7 digits is the minimum abbreviation of git, and 40
+/// digits is the full SHA1.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Sha1Abbrev(String);
+
+impl Sha1Abbrev {
+    /// This abbreviated SHA1 as string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for Sha1Abbrev {
+    type Error = Sha1AbbrevError;
+
+    /// Validate that `value` is an abbreviated SHA1.
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if !(7..=40).contains(&value.len()) {
+            Err(Sha1AbbrevError::Length(value))
+        } else if !value.chars().all(|c| c.is_ascii_hexdigit()) {
+            Err(Sha1AbbrevError::NotHex(value))
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for Sha1Abbrev {
+    type Error = Sha1AbbrevError;
+
+    /// Validate that `value` is an abbreviated SHA1.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::try_from(value.to_string())
+    }
+}
+
+impl From<Sha1Abbrev> for String {
+    fn from(sha1: Sha1Abbrev) -> Self {
+        sha1.0
+    }
+}
+
+impl fmt::Display for Sha1Abbrev {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A git commit, with its signature if any.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GitCommit {
+    pub(crate) abbrev_sha1: Sha1Abbrev,
+    pub(crate) signature: Option<CommitSignature>,
+}
+
+/// An AUR package, with evidence to determine trust in the package.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PackageWithEvidence {
+    pub(crate) name: String,
+    /// The package base of the package, if known.
+    #[serde(default)]
+    pub(crate) package_base: Option<String>,
+    /// All maintainers of the package, including the primary maintainer.
+    pub(crate) maintainers: HashSet<String>,
+    /// The primary maintainer of the package, if known.
+    pub(crate) primary_maintainer: Option<String>,
+    pub(crate) head_commit: GitCommit,
+    /// The number of votes for the package in the AUR, if known.
+    #[serde(default)]
+    pub(crate) num_votes: Option<u32>,
+}
+
+impl GitCommit {
+    /// Create a commit with the given abbreviated SHA1 and `signature`.
+    pub fn new(abbrev_sha1: Sha1Abbrev, signature: Option<CommitSignature>) -> Self {
+        Self {
+            abbrev_sha1,
+            signature,
+        }
+    }
+
+    /// The abbreviated SHA1 of this commit.
+    pub fn sha1(&self) -> &str {
+        self.abbrev_sha1.as_str()
+    }
+
+    /// The signature of this commit, if any.
+    pub fn signature(&self) -> Option<&CommitSignature> {
+        self.signature.as_ref()
+    }
+}
+
+/// The signature of a git tag.
+///
+/// Tags carry the same kind of signatures as commits.
+pub type TagSignature = CommitSignature;
+
+/// A git tag, with its signature if any.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GitTag {
+    pub(crate) name: String,
+    pub(crate) target: Sha1Abbrev,
+    pub(crate) signature: Option<TagSignature>,
+}
+
+impl GitTag {
+    /// Create a tag with the given `name`, pointing to the commit `target`, with `signature`.
+    pub fn new(name: String, target: Sha1Abbrev, signature: Option<TagSignature>) -> Self {
+        Self {
+            name,
+            target,
+            signature,
+        }
+    }
+
+    /// The name of this tag.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The abbreviated SHA1 of the commit this tag points to.
+    pub fn target(&self) -> &str {
+        self.target.as_str()
+    }
+
+    /// The signature of this tag, if any.
+    pub fn signature(&self) -> Option<&TagSignature> {
+        self.signature.as_ref()
+    }
+
+    /// Whether this tag points to the given `commit`.
+    ///
+    /// Both SHA1s may be abbreviated, so compare only their common prefix, ignoring case.
+    pub fn points_to(&self, commit: &GitCommit) -> bool {
+        let (target, sha1) = (self.target(), commit.sha1());
+        let len = target.len().min(sha1.len());
+        target[..len].eq_ignore_ascii_case(&sha1[..len])
+    }
+}
+
+impl PackageWithEvidence {
+    /// Create a package with the given `name`, `maintainers` and `head_commit`.
+    pub fn new(name: String, maintainers: HashSet<String>, head_commit: GitCommit) -> Self {
+        Self {
+            name,
+            package_base: None,
+            maintainers,
+            primary_maintainer: None,
+            head_commit,
+            num_votes: None,
+        }
+    }
+
+    /// Create a package from AUR information about the package and its `head_commit`.
+    ///
+    /// The maintainers of the package are the maintainer and all co-maintainers of `package`.
+    pub fn from_aur_package(package: &AurPackage, head_commit: GitCommit) -> Self {
+        let maintainers = package
+            .maintainer
+            .iter()
+            .chain(&package.co_maintainers)
+            .cloned()
+            .collect();
+        Self {
+            name: package.name.clone(),
+            package_base: Some(package.package_base.clone()),
+            maintainers,
+            primary_maintainer: package.maintainer.clone(),
+            head_commit,
+            num_votes: Some(package.num_votes),
+        }
+    }
+
+    /// The name of this package.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The package base of this package.
+    ///
+    /// If the package base is not known, assume that the package is its own base, like most AUR
+    /// packages.
+    pub fn package_base(&self) -> &str {
+        self.package_base.as_deref().unwrap_or(&self.name)
+    }
+
+    /// All maintainers of this package.
+    pub fn maintainers(&self) -> &HashSet<String> {
+        &self.maintainers
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::lattice::*;
-    use crate::trust::Trust;
-    use quickcheck::Gen;
+    use crate::trust::*;
     use quickcheck_macros::quickcheck;
+    use std::collections::HashSet;
 
     #[test]
     fn trust_default() {
@@ -85,11 +1234,149 @@ mod test {
         assert!(Trust::Untrusted >= Trust::Untrusted);
     }
 
-    impl quickcheck::Arbitrary for Trust {
-        fn arbitrary(g: &mut Gen) -> Self {
-            g.choose(&[Trust::Trusted, Trust::Indeterminate, Trust::Untrusted])
-                .unwrap()
-                .to_owned()
+    fn mixed_reasons(trust: Trust) -> TrustVerdict {
+        TrustVerdict::new(trust)
+            .add_reason(TrustReason::NoSignature {
+                sha1: "ae5a1b0".into(),
+            })
+            .add_reason(TrustReason::MaintainerDistrusted {
+                maintainer: "foo".into(),
+            })
+            .add_reason(TrustReason::MaintainerNotTrusted {
+                maintainer: "bar".into(),
+            })
+    }
+
+    #[test]
+    fn verdict_reasons_matching() {
+        let verdict = mixed_reasons(Trust::Untrusted);
+        assert_eq!(
+            verdict.reasons_matching(|reason| matches!(
+                reason,
+                TrustReason::MaintainerDistrusted { .. } | TrustReason::MaintainerNotTrusted { .. }
+            )),
+            [&verdict.reasons()[1], &verdict.reasons()[2]]
+        );
+        assert_eq!(
+            verdict.reasons_matching(|reason| reason.to_string().contains("not signed")),
+            [&verdict.reasons()[0]]
+        );
+        assert!(verdict
+            .reasons_matching(|reason| matches!(reason, TrustReason::Orphaned))
+            .is_empty());
+    }
+
+    #[test]
+    fn verdict_untrusted_reasons() {
+        let verdict = mixed_reasons(Trust::Untrusted);
+        assert_eq!(verdict.untrusted_reasons(), verdict.reasons());
+        assert!(mixed_reasons(Trust::Indeterminate)
+            .untrusted_reasons()
+            .is_empty());
+        assert!(mixed_reasons(Trust::Trusted).untrusted_reasons().is_empty());
+    }
+
+    #[test]
+    fn verdict_sort_untrusted_first() {
+        let mut verdicts = [
+            TrustVerdict::new(Trust::Trusted).add_reason(TrustReason::AllMaintainersTrusted),
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::MaintainersUnknown),
+            TrustVerdict::new(Trust::Untrusted).add_reason(TrustReason::Orphaned),
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::Orphaned),
+        ];
+        verdicts.sort();
+        assert_eq!(
+            verdicts.iter().map(TrustVerdict::trust).collect::<Vec<_>>(),
+            [
+                Trust::Untrusted,
+                Trust::Indeterminate,
+                Trust::Indeterminate,
+                Trust::Trusted
+            ]
+        );
+        assert_eq!(
+            verdicts[1],
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::MaintainersUnknown)
+        );
+    }
+
+    #[quickcheck]
+    fn verdict_ord_consistent_with_eq(left: TrustVerdict, right: TrustVerdict) {
+        assert_eq!(left.cmp(&right).is_eq(), left == right);
+        assert_eq!(left.cmp(&right).reverse(), right.cmp(&left));
+    }
+
+    #[quickcheck]
+    fn verdict_ord_meet_is_lesser(left: TrustVerdict, right: TrustVerdict) {
+        let meet = left.clone().meet(right.clone());
+        assert_eq!(meet.trust(), left.min(right).trust());
+    }
+
+    #[test]
+    fn verdict_mut() {
+        let mut verdict = TrustVerdict::default();
+        for maintainer in ["foo", "bar"] {
+            verdict.push_reason(TrustReason::MaintainerNotTrusted {
+                maintainer: maintainer.into(),
+            });
+        }
+        verdict.set_trust_mut(Trust::Untrusted);
+        assert_eq!(
+            verdict,
+            TrustVerdict::new(Trust::Untrusted)
+                .add_reason(TrustReason::MaintainerNotTrusted {
+                    maintainer: "foo".into()
+                })
+                .add_reason(TrustReason::MaintainerNotTrusted {
+                    maintainer: "bar".into()
+                })
+        );
+    }
+
+    #[test]
+    fn verdict_score() {
+        assert_eq!(TrustVerdict::new(Trust::Untrusted).score(), 0);
+        assert_eq!(TrustVerdict::new(Trust::Indeterminate).score(), 50);
+        assert_eq!(TrustVerdict::new(Trust::Trusted).score(), 100);
+        let unpopular =
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::NotEnoughVotes {
+                votes: 5,
+                min_votes: 10,
+            });
+        assert_eq!(unpopular.score(), 38);
+        assert_eq!(unpopular.set_trust(Trust::Untrusted).score(), 0);
+        let no_votes =
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::NotEnoughVotes {
+                votes: 0,
+                min_votes: 10,
+            });
+        assert_eq!(no_votes.score(), 25);
+    }
+
+    #[quickcheck]
+    fn verdict_score_monotonic(left: Trust, right: Trust, votes: u32, min_votes: u32) {
+        let verdict = |trust| {
+            TrustVerdict::new(trust).add_reason(TrustReason::NotEnoughVotes { votes, min_votes })
+        };
+        if left < right {
+            assert!(verdict(left).score() < verdict(right).score());
+            assert!(TrustVerdict::new(left).score() < verdict(right).score());
+        }
+        assert!(verdict(left).score() <= TrustVerdict::new(left).score());
+        assert!(verdict(left).score() <= 100);
+    }
+
+    #[test]
+    fn trust_hash() {
+        let trust = HashSet::from([
+            Trust::Untrusted,
+            Trust::Indeterminate,
+            Trust::Trusted,
+            Trust::Trusted,
+        ]);
+        assert_eq!(trust.len(), 3);
+        for t in [Trust::Untrusted, Trust::Indeterminate, Trust::Trusted] {
+            assert!(trust.contains(&t), "{:?}", t);
         }
     }
 
@@ -105,6 +1392,36 @@ mod test {
         assert_eq!(t.join(Trust::top()), Trust::Trusted);
     }
 
+    #[quickcheck]
+    fn trust_join_commutative(left: Trust, right: Trust) {
+        assert_eq!(left.join(right), right.join(left));
+    }
+
+    #[quickcheck]
+    fn trust_join_associative(a: Trust, b: Trust, c: Trust) {
+        assert_eq!(a.join(b).join(c), a.join(b.join(c)));
+    }
+
+    #[quickcheck]
+    fn trust_join_bottom_identity(t: Trust) {
+        assert_eq!(t.join(Trust::bottom()), t);
+    }
+
+    #[quickcheck]
+    fn trust_meet_commutative(left: Trust, right: Trust) {
+        assert_eq!(left.meet(right), right.meet(left));
+    }
+
+    #[quickcheck]
+    fn trust_meet_associative(a: Trust, b: Trust, c: Trust) {
+        assert_eq!(a.meet(b).meet(c), a.meet(b.meet(c)));
+    }
+
+    #[quickcheck]
+    fn trust_meet_top_identity(t: Trust) {
+        assert_eq!(t.meet(Trust::top()), t);
+    }
+
     #[quickcheck]
     fn trust_meet_bt(left: Trust, right: Trust) {
         let bottom = left.meet(right);
@@ -116,4 +1433,313 @@ mod test {
     fn trust_meet_bottom(t: Trust) {
         assert_eq!(t.meet(Trust::bottom()), Trust::Untrusted);
     }
+
+    #[quickcheck]
+    fn verdict_meet_commutative(left: TrustVerdict, right: TrustVerdict) {
+        assert_eq!(left.clone().meet(right.clone()), right.meet(left));
+    }
+
+    #[quickcheck]
+    fn verdict_meet_top_identity(verdict: TrustVerdict) {
+        assert_eq!(verdict.clone().meet(TrustVerdict::top()), verdict);
+        assert_eq!(TrustVerdict::top().meet(verdict.clone()), verdict);
+    }
+
+    #[quickcheck]
+    fn verdict_meet_trust(left: TrustVerdict, right: TrustVerdict) {
+        let trust = left.trust().meet(right.trust());
+        assert_eq!(left.meet(right).trust(), trust);
+    }
+
+    #[quickcheck]
+    fn verdict_join_trust(left: TrustVerdict, right: TrustVerdict) {
+        let trust = left.trust().join(right.trust());
+        assert_eq!(left.join(right).trust(), trust);
+    }
+
+    #[test]
+    fn verdict_meet_merges_reasons_of_equal_trust() {
+        let left =
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::MaintainerNotTrusted {
+                maintainer: "foo".into(),
+            });
+        let right =
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::MaintainersUnknown);
+        assert_eq!(
+            left.meet(right).reasons(),
+            [
+                TrustReason::MaintainersUnknown,
+                TrustReason::MaintainerNotTrusted {
+                    maintainer: "foo".into()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn verdict_map_reasons() {
+        let verdict = TrustVerdict::new(Trust::Trusted)
+            .add_reason(TrustReason::GoodSignature {
+                sha1: "ae5a1b0".into(),
+                signer: "Jane Doe <jane@example.com>".into(),
+                key: "SHA256:key".into(),
+            })
+            .add_reason(TrustReason::AllMaintainersTrusted)
+            .map_reasons(|reason| match reason {
+                TrustReason::GoodSignature { sha1, key, .. } => TrustReason::GoodSignature {
+                    sha1,
+                    signer: "<redacted>".into(),
+                    key,
+                },
+                other => other,
+            });
+        assert_eq!(verdict.trust(), Trust::Trusted);
+        assert_eq!(
+            verdict.reasons(),
+            [
+                TrustReason::GoodSignature {
+                    sha1: "ae5a1b0".into(),
+                    signer: "<redacted>".into(),
+                    key: "SHA256:key".into(),
+                },
+                TrustReason::AllMaintainersTrusted
+            ]
+        );
+    }
+
+    #[test]
+    fn verdict_json() {
+        let verdict = TrustVerdict::new(Trust::Trusted)
+            .add_reason(TrustReason::AllMaintainersTrusted)
+            .add_reason(TrustReason::NoSignature {
+                sha1: "ae5a1b0".into(),
+            });
+        let json = serde_json::to_string(&verdict).unwrap();
+        assert_eq!(
+            json,
+            r#"{"trust":"trusted","reasons":[{"kind":"all_maintainers_trusted"},{"kind":"no_signature","details":{"sha1":"ae5a1b0"}}]}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<TrustVerdict>(&json).unwrap(),
+            verdict
+        );
+    }
+
+    #[test]
+    fn trust_reason_json_roundtrip() {
+        let reasons = [
+            TrustReason::SignatureRequired,
+            TrustReason::ExpiredKey {
+                sha1: "ae5a1b0".into(),
+                signer: "Jane Doe <jane@example.com>".into(),
+                key: "SHA256:key".into(),
+                expired: Some(1669800000),
+            },
+            TrustReason::RecentlySubmitted {
+                age: Duration::from_secs(86400),
+                min_age: Duration::from_secs(30 * 86400),
+            },
+            TrustReason::TagSignature {
+                tag: "v1.0".into(),
+                validity: SignatureValidity::RevokedKey,
+                signer: "Jane Doe <jane@example.com>".into(),
+                key: "SHA256:key".into(),
+            },
+            TrustReason::TimedOut,
+            TrustReason::Other("Something else".into()),
+        ];
+        for reason in reasons {
+            let json = serde_json::to_string(&reason).unwrap();
+            assert_eq!(serde_json::from_str::<TrustReason>(&json).unwrap(), reason);
+        }
+        assert_eq!(
+            serde_json::to_string(&TrustReason::Other("Something else".into())).unwrap(),
+            r#"{"kind":"other","details":"Something else"}"#
+        );
+    }
+
+    #[quickcheck]
+    fn trust_reason_json_roundtrip_arbitrary(reason: TrustReason) -> bool {
+        let json = serde_json::to_string(&reason).unwrap();
+        serde_json::from_str::<TrustReason>(&json).unwrap() == reason
+    }
+
+    #[test]
+    fn meet_drops_duplicate_reasons() {
+        let verdict =
+            TrustVerdict::new(Trust::Indeterminate).add_reason(TrustReason::MaintainersUnknown);
+        assert_eq!(verdict.clone().meet(verdict.clone()), verdict);
+        assert_eq!(
+            verdict
+                .clone()
+                .meet(verdict.clone().add_reason(TrustReason::SignatureRequired))
+                .reasons(),
+            [
+                TrustReason::SignatureRequired,
+                TrustReason::MaintainersUnknown
+            ]
+        );
+    }
+
+    #[test]
+    fn signature_validity_severity() {
+        assert_eq!(SignatureValidity::Good.severity(), Severity::Ok);
+        assert_eq!(
+            SignatureValidity::UnknownValidity.severity(),
+            Severity::Warning
+        );
+        assert_eq!(
+            SignatureValidity::ExpiredSignature.severity(),
+            Severity::Warning
+        );
+        assert_eq!(SignatureValidity::ExpiredKey.severity(), Severity::Warning);
+        assert_eq!(
+            SignatureValidity::BadSignature.severity(),
+            Severity::Critical
+        );
+        assert_eq!(SignatureValidity::RevokedKey.severity(), Severity::Critical);
+    }
+
+    #[test]
+    fn trust_exit_code() {
+        assert_eq!(Trust::Trusted.exit_code(), 0);
+        assert_eq!(Trust::Indeterminate.exit_code(), 1);
+        assert_eq!(Trust::Untrusted.exit_code(), 2);
+    }
+
+    #[test]
+    fn trust_display() {
+        assert_eq!(Trust::Trusted.to_string(), "trusted");
+        assert_eq!(Trust::Indeterminate.to_string(), "indeterminate");
+        assert_eq!(Trust::Untrusted.to_string(), "untrusted");
+    }
+
+    #[test]
+    fn verdict_display() {
+        let verdict = TrustVerdict::new(Trust::Indeterminate)
+            .add_reason(TrustReason::NoSignature {
+                sha1: "ae5a1b0".into(),
+            })
+            .add_reason(TrustReason::MaintainerNotTrusted {
+                maintainer: "foo".into(),
+            });
+        assert_eq!(
+            verdict.to_string(),
+            "indeterminate
+  - Commit ae5a1b0 is not signed
+  - Maintainer foo is not trusted"
+        );
+        assert_eq!(TrustVerdict::new(Trust::Trusted).to_string(), "trusted");
+    }
+
+    #[test]
+    fn trust_json() {
+        assert_eq!(
+            serde_json::to_string(&Trust::Untrusted).unwrap(),
+            r#""untrusted""#
+        );
+        assert_eq!(
+            serde_json::to_string(&Trust::Indeterminate).unwrap(),
+            r#""indeterminate""#
+        );
+        assert_eq!(
+            serde_json::to_string(&Trust::Trusted).unwrap(),
+            r#""trusted""#
+        );
+    }
+
+    #[test]
+    fn package_with_evidence_roundtrip() {
+        let package = PackageWithEvidence {
+            name: "foo".into(),
+            package_base: Some("foo-base".into()),
+            maintainers: HashSet::from(["foo".into(), "bar".into()]),
+            primary_maintainer: Some("foo".into()),
+            head_commit: GitCommit {
+                abbrev_sha1: "ae5a1b0".try_into().unwrap(),
+                signature: Some(CommitSignature {
+                    validity: SignatureValidity::ExpiredKey,
+                    signer: "Jane Doe <jane@example.com>".into(),
+                    key_id: "SHA256:key".into(),
+                    fingerprint: None,
+                    algorithm: None,
+                    key_expiry: None,
+                    sig_timestamp: None,
+                }),
+            },
+            num_votes: None,
+        };
+        let json = serde_json::to_string(&package).unwrap();
+        let roundtrip: PackageWithEvidence = serde_json::from_str(&json).unwrap();
+        assert_eq!(roundtrip, package);
+    }
+
+    #[test]
+    fn sha1_abbrev_valid() {
+        for sha1 in [
+            "ae5a1b0",
+            "AE5A1B0",
+            "ae5a1b0d6f3c2e8b9a7d4c1f0e2b3a4c5d6e7f80",
+        ] {
+            assert_eq!(Sha1Abbrev::try_from(sha1).unwrap().as_str(), sha1);
+        }
+    }
+
+    #[test]
+    fn sha1_abbrev_invalid_length() {
+        assert_eq!(
+            Sha1Abbrev::try_from("ae5a1b"),
+            Err(Sha1AbbrevError::Length("ae5a1b".into()))
+        );
+        let too_long = "a".repeat(41);
+        assert_eq!(
+            Sha1Abbrev::try_from(too_long.as_str()),
+            Err(Sha1AbbrevError::Length(too_long))
+        );
+    }
+
+    #[test]
+    fn sha1_abbrev_not_hex() {
+        assert_eq!(
+            Sha1Abbrev::try_from("ae5a1bx"),
+            Err(Sha1AbbrevError::NotHex("ae5a1bx".into()))
+        );
+        assert!(serde_json::from_str::<Sha1Abbrev>(r#""HEAD~1234""#).is_err());
+    }
+
+    #[test]
+    fn git_commit_new() {
+        let unsigned = GitCommit::new("ae5a1b0".try_into().unwrap(), None);
+        assert_eq!(unsigned.sha1(), "ae5a1b0");
+        assert_eq!(unsigned.signature(), None);
+
+        let signature = CommitSignature {
+            validity: SignatureValidity::Good,
+            signer: "Jane Doe <jane@example.com>".into(),
+            key_id: "SHA256:key".into(),
+            fingerprint: None,
+            algorithm: None,
+            key_expiry: None,
+            sig_timestamp: None,
+        };
+        let signed = GitCommit::new("ae5a1b0".try_into().unwrap(), Some(signature.clone()));
+        assert_eq!(signed.sha1(), "ae5a1b0");
+        assert_eq!(signed.signature(), Some(&signature));
+    }
+
+    #[test]
+    fn package_with_evidence_from_aur_package() {
+        let aur_package: crate::aur::rpc::AurPackage = serde_json::from_str(
+            r#"{"Name":"aurutils","PackageBase":"aurutils","PackageBaseID":106013,"Maintainer":"Alad","CoMaintainers":["cgirard","rafasc"],"NumVotes":100,"Popularity":1.0,"FirstSubmitted":1447786390,"LastModified":1669131622,"OutOfDate":null}"#,
+        )
+        .unwrap();
+        let commit = GitCommit::new("ae5a1b0".try_into().unwrap(), None);
+        let package = PackageWithEvidence::from_aur_package(&aur_package, commit);
+        assert_eq!(package.name(), "aurutils");
+        assert_eq!(
+            package.maintainers(),
+            &HashSet::from(["Alad".into(), "cgirard".into(), "rafasc".into()])
+        );
+        assert_eq!(package.num_votes, Some(100));
+    }
 }